@@ -0,0 +1,160 @@
+// Measures how long parsing and solving of both parts takes. Each step is
+// measured separately: first it is run a few times to warm up (caches, branch
+// predictors, etc.), then it is run repeatedly and statistics of the individual
+// run times are computed.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, ensure};
+use solver::Solver;
+
+pub type Bench = fn(&str, &Options) -> Result<Timings>;
+
+pub const BENCHES: [Bench; 12] = [
+    bench::<day_1::Day1>,
+    bench::<day_2::Day2>,
+    bench::<day_3::Day3>,
+    bench::<day_4::Day4>,
+    bench::<day_5::Day5>,
+    bench::<day_6::Day6>,
+    bench::<day_7::Day7>,
+    bench::<day_8::Day8>,
+    bench::<day_9::Day9>,
+    bench::<day_10::Day10>,
+    bench::<day_11::Day11>,
+    bench::<day_12::Day12>,
+];
+
+#[derive(Copy, Clone, Debug)]
+pub struct Options {
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty());
+
+        samples.sort();
+
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+}
+
+impl Timings {
+    /// Returns the total median time of all steps.
+    pub fn total(&self) -> Duration {
+        self.parse.median + self.part_1.median + self.part_2.median
+    }
+}
+
+/// Measures parsing and solving of both parts of the puzzle (with default
+/// options of the day).
+pub fn bench<S: Solver>(input: &str, options: &Options) -> Result<Timings> {
+    ensure!(options.runs > 0, "number of runs must be positive");
+
+    let day_options = S::Options::default();
+
+    let parse = measure(options, || S::parse(input, &day_options))?;
+
+    let input = S::parse(input, &day_options).map_err(Into::into)?;
+
+    let part_1 = measure(options, || S::part_1(&input))?;
+    let part_2 = measure(options, || S::part_2(&input))?;
+
+    Ok(Timings {
+        parse,
+        part_1,
+        part_2,
+    })
+}
+
+fn measure<T, E>(options: &Options, mut f: impl FnMut() -> Result<T, E>) -> Result<Stats>
+where
+    E: Into<anyhow::Error>,
+{
+    for _ in 0..options.warmup {
+        black_box(f().map_err(Into::into)?);
+    }
+
+    let mut samples = Vec::with_capacity(options.runs);
+
+    for _ in 0..options.runs {
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed();
+
+        black_box(result.map_err(Into::into)?);
+        samples.push(elapsed);
+    }
+
+    Ok(Stats::from_samples(samples))
+}
+
+/// Formats timings of multiple days as a table with median times.
+pub fn format_table(timings: &[(usize, Timings)]) -> String {
+    let mut table = format!(
+        "{:>3}  {:>12}  {:>12}  {:>12}  {:>12}\n",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+
+    for (day, timings) in timings {
+        table += &format!(
+            "{:>3}  {:>12.3?}  {:>12.3?}  {:>12.3?}  {:>12.3?}\n",
+            day,
+            timings.parse.median,
+            timings.part_1.median,
+            timings.part_2.median,
+            timings.total()
+        );
+    }
+
+    table
+}
+
+/// Formats timings of multiple days as a JSON array. All times are in
+/// nanoseconds.
+pub fn format_json(timings: &[(usize, Timings)]) -> String {
+    let days = timings
+        .iter()
+        .map(|(day, timings)| {
+            format!(
+                r#"{{"day":{},"parse":{},"part1":{},"part2":{}}}"#,
+                day,
+                stats_json(&timings.parse),
+                stats_json(&timings.part_1),
+                stats_json(&timings.part_2)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("[{}]", days.join(","))
+}
+
+fn stats_json(stats: &Stats) -> String {
+    format!(
+        r#"{{"min_ns":{},"median_ns":{},"mean_ns":{}}}"#,
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.mean.as_nanos()
+    )
+}
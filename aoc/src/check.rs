@@ -0,0 +1,85 @@
+// Checks answers against expected answers stored locally. The expected answers
+// for each day are stored in a file with one line per part, i.e. in the same
+// format as the day binaries output them.
+
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::{RUNS, day_path, parts};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    Error(String),
+    Missing,
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Fail { .. } | Outcome::Error(_))
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {expected}, got {actual})")
+            }
+            Outcome::Error(message) => write!(f, "ERROR ({message})"),
+            Outcome::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Checks all parts of the given day.
+pub fn check_day(day: usize, inputs: &Path, answers: &Path) -> Result<Vec<(u32, Outcome)>> {
+    let input = read_optional(&day_path(inputs, day))?;
+    let expected = read_optional(&day_path(answers, day))?;
+
+    let expected = expected
+        .as_deref()
+        .map(|expected| expected.lines().map(str::to_owned).collect::<Vec<_>>());
+
+    let mut outcomes = vec![];
+
+    for &part in parts(day) {
+        let expected = expected
+            .as_ref()
+            .and_then(|expected| expected.get(part as usize - 1));
+
+        let (Some(input), Some(expected)) = (&input, expected) else {
+            outcomes.push((part, Outcome::Missing));
+            continue;
+        };
+
+        let report = RUNS[day - 1](day, input, Some(part));
+
+        let outcome = match (&report.error, report.answers().first()) {
+            (Some(error), _) => Outcome::Error(error.message.clone()),
+            (None, Some((_, answer))) if answer.to_string() == *expected => Outcome::Pass,
+            (None, answer) => Outcome::Fail {
+                expected: expected.clone(),
+                actual: answer.map_or(String::new(), |(_, answer)| answer.to_string()),
+            },
+        };
+
+        outcomes.push((part, outcome));
+    }
+
+    Ok(outcomes)
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("can't read {}", path.display())),
+    }
+}
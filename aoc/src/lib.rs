@@ -0,0 +1,37 @@
+use std::path::{Path, PathBuf};
+
+use solver::report::{self, Report};
+
+pub mod bench;
+pub mod check;
+
+/// Parses the input and solves the given part of the puzzle of the given day (or
+/// both parts if no part is given).
+pub type Run = fn(usize, &str, Option<u32>) -> Report;
+
+pub const RUNS: [Run; 12] = [
+    report::run::<day_1::Day1>,
+    report::run::<day_2::Day2>,
+    report::run::<day_3::Day3>,
+    report::run::<day_4::Day4>,
+    report::run::<day_5::Day5>,
+    report::run::<day_6::Day6>,
+    report::run::<day_7::Day7>,
+    report::run::<day_8::Day8>,
+    report::run::<day_9::Day9>,
+    report::run::<day_10::Day10>,
+    report::run::<day_11::Day11>,
+    report::run::<day_12::Day12>,
+];
+
+/// Returns the parts of the puzzle of the given day. As usual in Advent of Code,
+/// the puzzle of the last day has only one part.
+pub fn parts(day: usize) -> &'static [u32] {
+    if day == RUNS.len() { &[1] } else { &[1, 2] }
+}
+
+/// Returns the path of a file for the given day in a directory with inputs or
+/// answers (`<DIR>/day-<DAY>.txt`).
+pub fn day_path(dir: &Path, day: usize) -> PathBuf {
+    dir.join(format!("day-{day}.txt"))
+}
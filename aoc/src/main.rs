@@ -0,0 +1,353 @@
+// Runs solutions to any day's puzzle by its number. See `USAGE` below for
+// details.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use anyhow::{Context, Result, anyhow, bail};
+use aoc::bench::{self, BENCHES};
+use aoc::check::check_day;
+use aoc::{RUNS, day_path};
+use solver::report::Report;
+
+const USAGE: &str = "\
+usage: aoc run <DAY|all> [--part <PART>] [--inputs <DIR>] [--format <text|json>]
+       aoc check [<DAY|all>] [--inputs <DIR>] [--answers <DIR>]
+       aoc bench [<DAY|all>] [--inputs <DIR>] [--warmup <N>] [--runs <N>]
+                 [--format <text|json>]
+
+Commands:
+  run    Runs the solution to the puzzle of the given day (1-12), or of all
+         days. Input is read from the standard input, or from
+         <DIR>/day-<DAY>.txt when --inputs is given (required for \"all\").
+         When running all days, failing days are reported and skipped.
+         With --format json, a JSON object with answers, timings and error
+         (if any) is written for each day on a separate line.
+
+  check  Checks answers of the given day, or of all days (the default), against
+         expected answers stored in <DIR>/day-<DAY>.txt (one line per part).
+         Inputs and answers are read from the \"inputs\" and \"answers\"
+         directories by default.
+
+  bench  Measures parsing and solving of both parts of the given day, or of all
+         days with input (the default). Each step is run <N> times after a
+         warm-up (10 and 3 times by default) and median times are reported
+         (with --format json, minimum, median and mean times in nanoseconds
+         are reported). Inputs are read from the \"inputs\" directory by
+         default. Build with --release to get meaningful results.";
+
+#[derive(Copy, Clone, Debug)]
+enum Days {
+    One(usize),
+    All,
+}
+
+impl Days {
+    fn iter(self) -> impl Iterator<Item = usize> {
+        match self {
+            Days::One(day) => day..=day,
+            Days::All => 1..=RUNS.len(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug)]
+struct RunArgs {
+    days: Days,
+    part: Option<u32>,
+    inputs: Option<PathBuf>,
+    format: Format,
+}
+
+#[derive(Debug)]
+struct CheckArgs {
+    days: Days,
+    inputs: PathBuf,
+    answers: PathBuf,
+}
+
+#[derive(Debug)]
+struct BenchArgs {
+    days: Days,
+    inputs: PathBuf,
+    options: bench::Options,
+    format: Format,
+}
+
+#[derive(Debug)]
+enum Command {
+    Run(RunArgs),
+    Check(CheckArgs),
+    Bench(BenchArgs),
+}
+
+fn parse_args(args: &[String]) -> Result<Command> {
+    match args.first().map(String::as_str) {
+        Some("run") => Ok(Command::Run(parse_run_args(&args[1..])?)),
+        Some("check") => Ok(Command::Check(parse_check_args(&args[1..])?)),
+        Some("bench") => Ok(Command::Bench(parse_bench_args(&args[1..])?)),
+        Some(command) => bail!("unknown command: {command:?}"),
+        None => bail!("missing command"),
+    }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs> {
+    let mut args = args.iter();
+
+    let days = match args.next() {
+        Some(days) => parse_days(days)?,
+        None => bail!("missing day"),
+    };
+
+    let mut part = None;
+    let mut inputs = None;
+    let mut format = Format::Text;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => part = Some(parse_part(option_value(&mut args, arg)?)?),
+            "--inputs" => inputs = Some(PathBuf::from(option_value(&mut args, arg)?)),
+            "--format" => format = parse_format(option_value(&mut args, arg)?)?,
+            _ => bail!("unknown option: {arg:?}"),
+        }
+    }
+
+    if matches!(days, Days::All) && inputs.is_none() {
+        bail!("running all days requires --inputs");
+    }
+
+    Ok(RunArgs {
+        days,
+        part,
+        inputs,
+        format,
+    })
+}
+
+fn parse_check_args(args: &[String]) -> Result<CheckArgs> {
+    let mut args = args.iter().peekable();
+
+    let days = match args.next_if(|arg| !arg.starts_with("--")) {
+        Some(days) => parse_days(days)?,
+        None => Days::All,
+    };
+
+    let mut inputs = PathBuf::from("inputs");
+    let mut answers = PathBuf::from("answers");
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--inputs" => inputs = PathBuf::from(option_value(&mut args, arg)?),
+            "--answers" => answers = PathBuf::from(option_value(&mut args, arg)?),
+            _ => bail!("unknown option: {arg:?}"),
+        }
+    }
+
+    Ok(CheckArgs {
+        days,
+        inputs,
+        answers,
+    })
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs> {
+    let mut args = args.iter().peekable();
+
+    let days = match args.next_if(|arg| !arg.starts_with("--")) {
+        Some(days) => parse_days(days)?,
+        None => Days::All,
+    };
+
+    let mut inputs = PathBuf::from("inputs");
+    let mut options = bench::Options {
+        warmup: 3,
+        runs: 10,
+    };
+    let mut format = Format::Text;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--inputs" => inputs = PathBuf::from(option_value(&mut args, arg)?),
+            "--warmup" => options.warmup = parse_count(option_value(&mut args, arg)?)?,
+            "--runs" => options.runs = parse_count(option_value(&mut args, arg)?)?,
+            "--format" => format = parse_format(option_value(&mut args, arg)?)?,
+            _ => bail!("unknown option: {arg:?}"),
+        }
+    }
+
+    Ok(BenchArgs {
+        days,
+        inputs,
+        options,
+        format,
+    })
+}
+
+fn option_value<'a>(args: &mut impl Iterator<Item = &'a String>, option: &str) -> Result<&'a str> {
+    args.next()
+        .map(String::as_str)
+        .ok_or_else(|| anyhow!("missing value of option: {option:?}"))
+}
+
+fn parse_days(s: &str) -> Result<Days> {
+    if s == "all" {
+        return Ok(Days::All);
+    }
+
+    match s.parse::<usize>() {
+        Ok(day) if (1..=RUNS.len()).contains(&day) => Ok(Days::One(day)),
+        _ => bail!("invalid day: {s:?}"),
+    }
+}
+
+fn parse_part(s: &str) -> Result<u32> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => bail!("invalid part: {s:?}"),
+    }
+}
+
+fn parse_count(s: &str) -> Result<usize> {
+    s.parse().map_err(|_| anyhow!("invalid count: {s:?}"))
+}
+
+fn parse_format(s: &str) -> Result<Format> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => bail!("invalid format: {s:?}"),
+    }
+}
+
+fn read_input(inputs: Option<&Path>, day: usize) -> Result<String> {
+    match inputs {
+        Some(dir) => {
+            let path = day_path(dir, day);
+
+            fs::read_to_string(&path).with_context(|| format!("can't read {}", path.display()))
+        }
+        None => Ok(io::read_to_string(io::stdin())?),
+    }
+}
+
+fn run_day(day: usize, args: &RunArgs) -> Report {
+    match read_input(args.inputs.as_deref(), day) {
+        Ok(input) => RUNS[day - 1](day, &input, args.part),
+        Err(e) => Report::io_error(day, e),
+    }
+}
+
+fn run(args: &RunArgs) -> Result<()> {
+    let mut failure_count = 0;
+
+    for day in args.days.iter() {
+        let report = run_day(day, args);
+
+        match args.format {
+            Format::Text => {
+                for warning in &report.warnings {
+                    match args.days {
+                        Days::One(_) => eprintln!("warning: {warning}"),
+                        Days::All => eprintln!("day {day} warning: {warning}"),
+                    }
+                }
+
+                // When running all days, a failure is reported and the other
+                // days are still run, as in JSON.
+                if let Some(error) = report.error {
+                    match args.days {
+                        Days::One(_) => bail!("{}", error.message),
+                        Days::All => {
+                            eprintln!("day {day} failed: {}", error.message);
+                            failure_count += 1;
+                            continue;
+                        }
+                    }
+                }
+
+                for (part, answer) in report.answers() {
+                    match args.days {
+                        Days::One(_) => println!("{answer}"),
+                        Days::All => println!("day {day} part {part}: {answer}"),
+                    }
+                }
+            }
+            Format::Json => {
+                if report.error.is_some() {
+                    failure_count += 1;
+                }
+
+                println!("{}", report.to_json());
+            }
+        }
+    }
+
+    if failure_count > 0 {
+        bail!("{failure_count} day(s) failed");
+    }
+
+    Ok(())
+}
+
+fn check(args: &CheckArgs) -> Result<()> {
+    let mut failure_count = 0;
+
+    for day in args.days.iter() {
+        for (part, outcome) in check_day(day, &args.inputs, &args.answers)? {
+            if outcome.is_failure() {
+                failure_count += 1;
+            }
+
+            println!("day {day} part {part}: {outcome}");
+        }
+    }
+
+    if failure_count > 0 {
+        bail!("{failure_count} check(s) failed");
+    }
+
+    Ok(())
+}
+
+fn bench(args: &BenchArgs) -> Result<()> {
+    let mut timings = vec![];
+
+    for day in args.days.iter() {
+        // When measuring all days, skip the ones without input.
+        if matches!(args.days, Days::All) && !day_path(&args.inputs, day).exists() {
+            continue;
+        }
+
+        let input = read_input(Some(&args.inputs), day)?;
+        let day_timings =
+            BENCHES[day - 1](&input, &args.options).with_context(|| format!("day {day} failed"))?;
+
+        timings.push((day, day_timings));
+    }
+
+    match args.format {
+        Format::Text => print!("{}", bench::format_table(&timings)),
+        Format::Json => println!("{}", bench::format_json(&timings)),
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let command = parse_args(&args).map_err(|e| anyhow!("{e}\n\n{USAGE}"))?;
+
+    match command {
+        Command::Run(args) => run(&args),
+        Command::Check(args) => check(&args),
+        Command::Bench(args) => bench(&args),
+    }
+}
@@ -0,0 +1,76 @@
+// Checks answers of all days against expected answers stored locally in the
+// `inputs` and `answers` directories at the root of the workspace (see
+// `aoc::check`). Days without a stored input or answer are skipped.
+
+use std::path::Path;
+
+use aoc::check::check_day;
+
+fn check(day: usize) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let outcomes = check_day(day, &root.join("inputs"), &root.join("answers")).unwrap();
+
+    for (part, outcome) in outcomes {
+        assert!(!outcome.is_failure(), "day {day} part {part}: {outcome}");
+    }
+}
+
+#[test]
+fn day_1() {
+    check(1);
+}
+
+#[test]
+fn day_2() {
+    check(2);
+}
+
+#[test]
+fn day_3() {
+    check(3);
+}
+
+#[test]
+fn day_4() {
+    check(4);
+}
+
+#[test]
+fn day_5() {
+    check(5);
+}
+
+#[test]
+fn day_6() {
+    check(6);
+}
+
+#[test]
+fn day_7() {
+    check(7);
+}
+
+#[test]
+fn day_8() {
+    check(8);
+}
+
+#[test]
+fn day_9() {
+    check(9);
+}
+
+#[test]
+fn day_10() {
+    check(10);
+}
+
+#[test]
+fn day_11() {
+    check(11);
+}
+
+#[test]
+fn day_12() {
+    check(12);
+}
@@ -0,0 +1,78 @@
+// Runs all implementations of the solution (see `variant` in the library) on the
+// input from the standard input, writes their answers and reports where they
+// differ from the reference implementation. Exits with an error if they do.
+// A variant that panics is reported as differing, too.
+
+use std::{env, io};
+
+use anyhow::{Result, anyhow, bail, ensure};
+use day_1::DialOptions;
+use day_1::variant::{self, Variant};
+
+fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let options = DialOptions::from_args(&args)
+        .and_then(|options| {
+            if options.trace.is_some() {
+                bail!("unknown option: \"--trace\"");
+            }
+
+            Ok(options)
+        })
+        .map_err(|e| anyhow!("{e}\n\nusage: compare [--size <N>] [--start <POS>]"))?;
+
+    let input = io::read_to_string(io::stdin())?;
+    let outcomes = Variant::ALL.map(|variant| {
+        (
+            variant,
+            variant.run_catching_panics(options.size, options.start, &input),
+        )
+    });
+
+    for (variant, outcome) in &outcomes {
+        match outcome {
+            Ok(Ok(outcome)) => println!(
+                "{}: {} {}",
+                variant.name(),
+                outcome.zero_count_1,
+                outcome.zero_count_2
+            ),
+            Ok(Err(e)) => println!("{}: error: {e:#}", variant.name()),
+            Err(message) => println!("{}: panicked: {message}", variant.name()),
+        }
+    }
+
+    let (reference, expected) = &outcomes[0];
+
+    let Ok(expected) = expected else {
+        bail!("{} panicked", reference.name());
+    };
+
+    let mut discrepancy_count = 0;
+
+    for (variant, outcome) in &outcomes[1..] {
+        let discrepancy = match outcome {
+            Ok(outcome) => variant::discrepancy(expected, outcome),
+            Err(message) => Some(format!("panicked: {message}")),
+        };
+
+        if let Some(discrepancy) = discrepancy {
+            println!(
+                "{} differs from {}: {discrepancy}",
+                variant.name(),
+                reference.name()
+            );
+            discrepancy_count += 1;
+        }
+    }
+
+    ensure!(
+        discrepancy_count == 0,
+        "{discrepancy_count} of {} variants differ from {}",
+        outcomes.len() - 1,
+        reference.name()
+    );
+
+    println!("all variants agree");
+    Ok(())
+}
@@ -0,0 +1,1083 @@
+use std::error::Error;
+use std::fmt;
+
+use anyhow::{bail, ensure};
+use input::{Line, ParseError};
+use solver::{Options, Solver, option_value};
+
+pub mod simple;
+pub mod structured;
+pub mod variant;
+
+/// Size of the dial in the puzzle (number of positions).
+pub const DEFAULT_SIZE: u64 = 100;
+
+/// Position the dial points at in the beginning in the puzzle.
+pub const DEFAULT_START: u64 = 50;
+
+/// Counts of the times the dial pointed at a target position: `landings` is the
+/// number of rotations that ended at the target, `passes` is the number of
+/// clicks after which the dial pointed at it (including the ends of rotations).
+///
+/// For target 0, they are the same as `zero_count_1` and `zero_count_2`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct TargetCount {
+    pub target: u64,
+    pub landings: u64,
+    pub passes: u64,
+}
+
+/// Record of a rotation of a dial with tracing enabled.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct TraceEntry {
+    /// Number of the rotation among those applied to the dial, starting from 1
+    /// (the dial doesn't know which input lines the rotations come from).
+    pub index: usize,
+    pub rotation: Rotation,
+    pub pos_before: u64,
+    pub pos_after: u64,
+    /// Number of clicks after which the dial pointed at 0 (including the end of
+    /// the rotation).
+    pub zero_passes: u64,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TraceFormat {
+    Csv,
+    Json,
+}
+
+/// Formats a trace as CSV (with a header) or as a JSON array of objects.
+pub fn format_trace(trace: &[TraceEntry], format: TraceFormat) -> String {
+    let mut output = match format {
+        TraceFormat::Csv => "index,rotation,pos_before,pos_after,zero_passes\n".to_owned(),
+        TraceFormat::Json => "[".to_owned(),
+    };
+
+    for (i, entry) in trace.iter().enumerate() {
+        let TraceEntry {
+            index,
+            rotation,
+            pos_before,
+            pos_after,
+            zero_passes,
+        } = entry;
+
+        match format {
+            TraceFormat::Csv => {
+                output += &format!("{index},{rotation},{pos_before},{pos_after},{zero_passes}\n");
+            }
+            TraceFormat::Json => {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                output += &format!(
+                    r#"{{"index":{index},"rotation":"{rotation}","pos_before":{pos_before},"pos_after":{pos_after},"zero_passes":{zero_passes}}}"#
+                );
+            }
+        }
+    }
+
+    if format == TraceFormat::Json {
+        output.push_str("]\n");
+    }
+
+    output
+}
+
+/// Answers to both parts together with the trace of all rotations, as computed
+/// by one of the implementations of the solution (see `variant`).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Outcome {
+    pub zero_count_1: u64,
+    pub zero_count_2: u64,
+    pub trace: Vec<TraceEntry>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Dial {
+    size: u64,
+    pos: u64,
+    zero_count_1: u64,
+    zero_count_2: u64,
+    target_counts: Vec<TargetCount>,
+    trace: Option<Vec<TraceEntry>>,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Dial::new()
+    }
+}
+
+impl Dial {
+    pub fn new() -> Dial {
+        Dial::with_size(DEFAULT_SIZE, DEFAULT_START)
+    }
+
+    /// Creates a dial with the given number of positions, pointing at the given
+    /// position.
+    ///
+    /// Panics if the size is zero or the position is out of range.
+    pub fn with_size(size: u64, start: u64) -> Dial {
+        assert!(size > 0);
+        assert!(start < size);
+
+        Dial {
+            size,
+            pos: start,
+            zero_count_1: 0,
+            zero_count_2: 0,
+            target_counts: vec![],
+            trace: None,
+        }
+    }
+
+    /// Makes the dial record all rotations (see `trace`).
+    pub fn with_trace(mut self) -> Dial {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Makes the dial count landings on and passes through the given target
+    /// positions (see `target_counts`).
+    ///
+    /// Panics if any of the positions is out of range.
+    pub fn with_targets(mut self, targets: &[u64]) -> Dial {
+        assert!(targets.iter().all(|&target| target < self.size));
+
+        self.target_counts = targets
+            .iter()
+            .map(|&target| TargetCount {
+                target,
+                landings: 0,
+                passes: 0,
+            })
+            .collect();
+
+        self
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn pos(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn zero_count_1(&self) -> u64 {
+        self.zero_count_1
+    }
+
+    pub fn zero_count_2(&self) -> u64 {
+        self.zero_count_2
+    }
+
+    /// Returns counts for the targets given to `with_targets`, in the same
+    /// order.
+    pub fn target_counts(&self) -> &[TargetCount] {
+        &self.target_counts
+    }
+
+    /// Returns records of all rotations if tracing was enabled by
+    /// `with_trace`.
+    pub fn trace(&self) -> Option<&[TraceEntry]> {
+        self.trace.as_deref()
+    }
+
+    pub fn apply(&mut self, rotation: Rotation) {
+        match rotation {
+            Rotation::Left(n) => self.rotate_left(n),
+            Rotation::Right(n) => self.rotate_right(n),
+        }
+    }
+
+    pub fn rotate_left(&mut self, n: u64) {
+        // The trick I use here is to convert a left rotation to a right one
+        // using a symmetry along the vertical axis of the dial, which can be
+        // expressed as:
+        //
+        //     pos <=> (N - pos) % N
+        //
+        // This approach simplifies the counting of zero crossings.
+        //
+        // There may be a simple way to count zero crossings for left rotations
+        // even without using the symmetry, but I don't see it at the moment.
+
+        let (pos, zero_count_1, zero_count_2) = self.rotate(self.symmetrical_pos(self.pos), n);
+
+        self.update_target_counts(n, true);
+        self.record(Rotation::Left(n), self.symmetrical_pos(pos), zero_count_2);
+        self.pos = self.symmetrical_pos(pos);
+        self.zero_count_1 += zero_count_1;
+        self.zero_count_2 += zero_count_2;
+    }
+
+    pub fn rotate_right(&mut self, n: u64) {
+        let (pos, zero_count_1, zero_count_2) = self.rotate(self.pos, n);
+
+        self.update_target_counts(n, false);
+        self.record(Rotation::Right(n), pos, zero_count_2);
+        self.pos = pos;
+        self.zero_count_1 += zero_count_1;
+        self.zero_count_2 += zero_count_2;
+    }
+
+    /// Undoes a rotation, which must be the last one applied to the dial. The
+    /// position and all counts are restored, and the rotation is removed from
+    /// the trace.
+    ///
+    /// Panics if it's obvious that the rotation wasn't the last one applied.
+    pub fn undo(&mut self, rotation: Rotation) {
+        let mut dial = Dial::with_size(self.size, self.pos);
+        dial.apply(rotation.inverse());
+
+        let pos_before = dial.pos;
+
+        // Apply the rotation again to find out how much it added to the counts.
+        let targets = self
+            .target_counts
+            .iter()
+            .map(|count| count.target)
+            .collect::<Vec<_>>();
+
+        let mut dial = Dial::with_size(self.size, pos_before).with_targets(&targets);
+        dial.apply(rotation);
+
+        let sub = |count: u64, delta: u64| {
+            count
+                .checked_sub(delta)
+                .expect("rotation wasn't applied to the dial")
+        };
+
+        self.pos = pos_before;
+        self.zero_count_1 = sub(self.zero_count_1, dial.zero_count_1);
+        self.zero_count_2 = sub(self.zero_count_2, dial.zero_count_2);
+
+        for (count, delta) in self.target_counts.iter_mut().zip(&dial.target_counts) {
+            count.landings = sub(count.landings, delta.landings);
+            count.passes = sub(count.passes, delta.passes);
+        }
+
+        if let Some(trace) = &mut self.trace {
+            let entry = trace.pop().expect("rotation wasn't applied to the dial");
+
+            assert_eq!(
+                entry.rotation, rotation,
+                "rotation wasn't the last one applied"
+            );
+        }
+    }
+
+    /// Returns the shortest rotation after which `zero_count_1` would be equal
+    /// to the given count, or `None` if it's not possible with one rotation.
+    pub fn min_rotation_for_zero_count_1(&self, count: u64) -> Option<Rotation> {
+        match count.checked_sub(self.zero_count_1)? {
+            // Any rotation not ending at 0 will do.
+            0 if self.pos != 0 => Some(Rotation::Right(0)),
+            0 if self.size > 1 => Some(Rotation::Right(1)),
+            // Even an empty rotation counts when the dial points at 0.
+            1 if self.pos == 0 => Some(Rotation::Right(0)),
+            1 => self.min_rotation_to_zero(0),
+            _ => None,
+        }
+    }
+
+    /// Returns the shortest rotation after which `zero_count_2` would be equal
+    /// to the given count, or `None` if it's not possible with one rotation
+    /// (i.e. if the count is lower than the current one, or if the number of
+    /// clicks would overflow).
+    pub fn min_rotation_for_zero_count_2(&self, count: u64) -> Option<Rotation> {
+        match count.checked_sub(self.zero_count_2)? {
+            0 => Some(Rotation::Right(0)),
+            passes => self.min_rotation_to_zero((passes - 1).checked_mul(self.size)?),
+        }
+    }
+
+    // Returns the shortest rotation that gets the dial to 0 and then makes the
+    // given number of additional clicks (which should be a multiple of the
+    // size), or `None` if the number of clicks would overflow.
+    fn min_rotation_to_zero(&self, extra: u64) -> Option<Rotation> {
+        // When the dial points at 0, it takes a full turn to get back to it.
+        let (left, right) = match self.pos {
+            0 => (self.size, self.size),
+            pos => (pos, self.size - pos),
+        };
+
+        if left < right {
+            left.checked_add(extra).map(Rotation::Left)
+        } else {
+            right.checked_add(extra).map(Rotation::Right)
+        }
+    }
+
+    fn record(&mut self, rotation: Rotation, pos_after: u64, zero_passes: u64) {
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                index: trace.len() + 1,
+                rotation,
+                pos_before: self.pos,
+                pos_after,
+                zero_passes,
+            });
+        }
+    }
+
+    // Updates target counts for a rotation by `n` clicks from the current
+    // position.
+    fn update_target_counts(&mut self, n: u64, left: bool) {
+        // Counting each target is O(1): once we know how many clicks it takes to
+        // reach the target for the first time, it's reached again after every
+        // full turn of the dial.
+        for count in &mut self.target_counts {
+            let (from, to) = if left {
+                (count.target, self.pos)
+            } else {
+                (self.pos, count.target)
+            };
+
+            let distance = match to.checked_sub(from) {
+                Some(0) => self.size,
+                Some(distance) => distance,
+                None => self.size - (from - to),
+            };
+
+            if n >= distance {
+                let passes = (n - distance) / self.size + 1;
+
+                count.passes += passes;
+
+                if (n - distance).is_multiple_of(self.size) {
+                    count.landings += 1;
+                }
+            } else if n == 0 && self.pos == count.target {
+                // Rotation by 0 clicks ends where it starts.
+                count.landings += 1;
+            }
+        }
+    }
+
+    fn symmetrical_pos(&self, pos: u64) -> u64 {
+        (self.size - pos) % self.size
+    }
+
+    fn rotate(&self, pos: u64, n: u64) -> (u64, u64, u64) {
+        // Use 128-bit arithmetic, so that the sum doesn't overflow even for
+        // the largest dials.
+        let p = pos as u128 + n as u128;
+        let size = self.size as u128;
+
+        let pos = (p % size) as u64;
+        let zero_count_1 = if pos == 0 { 1 } else { 0 };
+        let zero_count_2 = (p / size) as u64;
+
+        (pos, zero_count_1, zero_count_2)
+    }
+}
+
+/// Size and starting position of the dial, and the format of the trace if it
+/// should be written (instead of the answers).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct DialOptions {
+    pub size: u64,
+    pub start: u64,
+    pub trace: Option<TraceFormat>,
+}
+
+impl Default for DialOptions {
+    fn default() -> Self {
+        DialOptions {
+            size: DEFAULT_SIZE,
+            start: DEFAULT_START,
+            trace: None,
+        }
+    }
+}
+
+impl DialOptions {
+    /// Parses options from command line arguments (for binaries not using
+    /// `solver::main`).
+    pub fn from_args(args: &[String]) -> anyhow::Result<DialOptions> {
+        let mut options = DialOptions::default();
+        let mut args = args.iter().map(String::as_str);
+
+        while let Some(arg) = args.next() {
+            if !options.parse_option(arg, &mut args)? {
+                bail!("unknown option: {arg:?}");
+            }
+        }
+
+        options.validate()?;
+
+        Ok(options)
+    }
+
+    pub fn dial(&self) -> Dial {
+        Dial::with_size(self.size, self.start)
+    }
+}
+
+impl Options for DialOptions {
+    const USAGE: &'static str = "[--size <N>] [--start <POS>] [--trace <csv|json>]";
+
+    fn parse_option(
+        &mut self,
+        option: &str,
+        args: &mut dyn Iterator<Item = &str>,
+    ) -> anyhow::Result<bool> {
+        let value = match option {
+            "--size" | "--start" | "--trace" => option_value(args, option)?,
+            _ => return Ok(false),
+        };
+
+        if option == "--trace" {
+            self.trace = match value {
+                "csv" => Some(TraceFormat::Csv),
+                "json" => Some(TraceFormat::Json),
+                _ => bail!("invalid trace format: {value:?}"),
+            };
+
+            return Ok(true);
+        }
+
+        let Ok(value) = value.parse() else {
+            bail!("invalid value of option {option:?}: {value:?}");
+        };
+
+        match option {
+            "--size" => self.size = value,
+            _ => self.start = value,
+        }
+
+        Ok(true)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        ensure!(self.size > 0, "dial size must be positive");
+        ensure!(
+            self.start < self.size,
+            "starting position must be less than dial size"
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Rotation {
+    Left(u64),
+    Right(u64),
+}
+
+impl Rotation {
+    /// Returns the rotation in the opposite direction by the same number of
+    /// clicks.
+    pub fn inverse(self) -> Rotation {
+        match self {
+            Rotation::Left(n) => Rotation::Right(n),
+            Rotation::Right(n) => Rotation::Left(n),
+        }
+    }
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rotation::Left(n) => write!(f, "L{n}"),
+            Rotation::Right(n) => write!(f, "R{n}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseLineError {
+    MissingInstruction,
+    InvalidDirection(char),
+    InvalidDistance(String),
+}
+
+impl fmt::Display for ParseLineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseLineError::MissingInstruction => {
+                write!(f, "missing instruction")
+            }
+            ParseLineError::InvalidDirection(ch) => {
+                write!(f, "invalid direction: {ch:?}")
+            }
+            ParseLineError::InvalidDistance(s) => {
+                write!(f, "invalid distance: {s:?}")
+            }
+        }
+    }
+}
+
+impl Error for ParseLineError {}
+
+pub fn parse_line(line: &str) -> Result<Rotation, ParseLineError> {
+    if line.is_empty() {
+        return Err(ParseLineError::MissingInstruction);
+    }
+
+    let direction = line.chars().next().unwrap();
+    let distance = &line[direction.len_utf8()..];
+
+    match direction {
+        'L' => {
+            let distance = distance
+                .parse::<u64>()
+                .map_err(|_| ParseLineError::InvalidDistance(distance.to_owned()))?;
+
+            Ok(Rotation::Left(distance))
+        }
+
+        'R' => {
+            let distance = distance
+                .parse::<u64>()
+                .map_err(|_| ParseLineError::InvalidDistance(distance.to_owned()))?;
+
+            Ok(Rotation::Right(distance))
+        }
+
+        _ => Err(ParseLineError::InvalidDirection(direction)),
+    }
+}
+
+// Parses a line as a rotation, with errors pointing to the invalid part of it.
+fn parse_rotation(line: &Line) -> Result<Rotation, ParseError> {
+    parse_line(line.text).map_err(|e| {
+        let part = match e {
+            ParseLineError::MissingInstruction => line.text,
+            ParseLineError::InvalidDirection(direction) => &line.text[..direction.len_utf8()],
+            ParseLineError::InvalidDistance(_) => &line.text[1..],
+        };
+
+        line.error_at(part, e.to_string())
+    })
+}
+
+/// Returns the starting position of a dial of the given size that ends at the
+/// given position after the given rotations.
+pub fn find_start(size: u64, end: u64, rotations: &[Rotation]) -> u64 {
+    assert!(end < size);
+
+    let size = size as u128;
+
+    // To get back to the start, the rotations need to be undone, i.e. the dial
+    // needs to be rotated right by the left rotations and left by the right
+    // ones. Only the total matters, and rotating left by n clicks is the same
+    // as rotating right by `size - n % size` clicks.
+    let right = rotations
+        .iter()
+        .map(|rotation| match *rotation {
+            Rotation::Left(n) => n as u128 % size,
+            Rotation::Right(n) => (size - n as u128 % size) % size,
+        })
+        .fold(0, |sum, n| (sum + n) % size);
+
+    ((end as u128 + right) % size) as u64
+}
+
+/// Rotations of the dial, together with the dial in its initial state.
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub dial: Dial,
+    pub rotations: Vec<Rotation>,
+}
+
+fn rotate_dial(input: &Input) -> Dial {
+    let Input { dial, rotations } = input;
+    let mut dial = dial.clone();
+
+    for &rotation in rotations {
+        dial.apply(rotation);
+    }
+
+    dial
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = Input;
+    type Options = DialOptions;
+    type Answer1 = u64;
+    type Answer2 = u64;
+    type Error = ParseError;
+
+    fn parse(input: &str, options: &DialOptions) -> Result<Input, ParseError> {
+        let rotations = input::lines(input)
+            .map(|line| parse_rotation(&line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Input {
+            dial: options.dial(),
+            rotations,
+        })
+    }
+
+    fn part_1(input: &Input) -> Result<u64, ParseError> {
+        Ok(rotate_dial(input).zero_count_1())
+    }
+
+    fn part_2(input: &Input) -> Result<u64, ParseError> {
+        Ok(rotate_dial(input).zero_count_2())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    // Rotates the dial click by click and returns the final position and both
+    // zero counts.
+    fn rotate_naively(size: u64, start: u64, rotations: &[Rotation]) -> (u64, u64, u64) {
+        let mut pos = start;
+        let mut zero_count_1 = 0;
+        let mut zero_count_2 = 0;
+
+        for rotation in rotations {
+            let (n, step) = match *rotation {
+                Rotation::Left(n) => (n, size - 1),
+                Rotation::Right(n) => (n, 1),
+            };
+
+            for _ in 0..n {
+                pos = (pos + step) % size;
+
+                if pos == 0 {
+                    zero_count_2 += 1;
+                }
+            }
+
+            if pos == 0 {
+                zero_count_1 += 1;
+            }
+        }
+
+        (pos, zero_count_1, zero_count_2)
+    }
+
+    #[test]
+    fn dial_works() {
+        let mut dial = Dial::new();
+        assert_eq!(dial.pos(), 50);
+        assert_eq!(dial.zero_count_1(), 0);
+        assert_eq!(dial.zero_count_2(), 0);
+
+        dial.rotate_left(68);
+        assert_eq!(dial.pos(), 82);
+        assert_eq!(dial.zero_count_1(), 0);
+        assert_eq!(dial.zero_count_2(), 1);
+
+        dial.rotate_left(30);
+        assert_eq!(dial.pos(), 52);
+        assert_eq!(dial.zero_count_1(), 0);
+        assert_eq!(dial.zero_count_2(), 1);
+
+        dial.rotate_right(48);
+        assert_eq!(dial.pos(), 0);
+        assert_eq!(dial.zero_count_1(), 1);
+        assert_eq!(dial.zero_count_2(), 2);
+
+        dial.rotate_left(5);
+        assert_eq!(dial.pos(), 95);
+        assert_eq!(dial.zero_count_1(), 1);
+        assert_eq!(dial.zero_count_2(), 2);
+
+        dial.rotate_right(60);
+        assert_eq!(dial.pos(), 55);
+        assert_eq!(dial.zero_count_1(), 1);
+        assert_eq!(dial.zero_count_2(), 3);
+
+        dial.rotate_left(55);
+        assert_eq!(dial.pos(), 0);
+        assert_eq!(dial.zero_count_1(), 2);
+        assert_eq!(dial.zero_count_2(), 4);
+
+        dial.rotate_left(1);
+        assert_eq!(dial.pos(), 99);
+        assert_eq!(dial.zero_count_1(), 2);
+        assert_eq!(dial.zero_count_2(), 4);
+
+        dial.rotate_left(99);
+        assert_eq!(dial.pos(), 0);
+        assert_eq!(dial.zero_count_1(), 3);
+        assert_eq!(dial.zero_count_2(), 5);
+
+        dial.rotate_right(14);
+        assert_eq!(dial.pos(), 14);
+        assert_eq!(dial.zero_count_1(), 3);
+        assert_eq!(dial.zero_count_2(), 5);
+
+        dial.rotate_left(82);
+        assert_eq!(dial.pos(), 32);
+        assert_eq!(dial.zero_count_1(), 3);
+        assert_eq!(dial.zero_count_2(), 6);
+    }
+
+    #[test]
+    fn dial_with_size_works() {
+        let mut dial = Dial::with_size(7, 0);
+
+        dial.rotate_left(7);
+        assert_eq!(dial.pos(), 0);
+        assert_eq!(dial.zero_count_1(), 1);
+        assert_eq!(dial.zero_count_2(), 1);
+
+        dial.rotate_right(16);
+        assert_eq!(dial.pos(), 2);
+        assert_eq!(dial.zero_count_1(), 1);
+        assert_eq!(dial.zero_count_2(), 3);
+
+        let mut dial = Dial::with_size(u64::MAX, u64::MAX - 1);
+
+        dial.rotate_right(3);
+        assert_eq!(dial.pos(), 2);
+        assert_eq!(dial.zero_count_2(), 1);
+
+        dial.rotate_left(u64::MAX);
+        assert_eq!(dial.pos(), 2);
+        assert_eq!(dial.zero_count_2(), 2);
+    }
+
+    #[test]
+    fn dial_options_work() {
+        let args = ["--size", "10", "--start", "3", "--trace", "csv"].map(String::from);
+
+        assert_eq!(
+            DialOptions::from_args(&args).unwrap(),
+            DialOptions {
+                size: 10,
+                start: 3,
+                trace: Some(TraceFormat::Csv),
+            }
+        );
+
+        let args = ["--start", "100"].map(String::from);
+        assert!(DialOptions::from_args(&args).is_err());
+
+        let args = ["--size", "0", "--start", "0"].map(String::from);
+        assert!(DialOptions::from_args(&args).is_err());
+    }
+
+    #[test]
+    fn parse_works() {
+        let input = Day1::parse("L68\nR30\n", &DialOptions::default()).unwrap();
+        assert_eq!(
+            input.rotations,
+            vec![Rotation::Left(68), Rotation::Right(30)]
+        );
+
+        for (input, column, message) in [
+            ("L68\n\n", 1, "missing instruction"),
+            ("L68\nX30\n", 1, "invalid direction: 'X'"),
+            ("L68\nR3x\n", 2, "invalid distance: \"3x\""),
+            ("L68\nÄ30\n", 1, "invalid direction: 'Ä'"),
+        ] {
+            let error = Day1::parse(input, &DialOptions::default()).unwrap_err();
+
+            assert_eq!((error.line(), error.column()), (2, column), "{input:?}");
+            assert_eq!(error.message(), message, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn target_counts_work() {
+        let mut dial = Dial::with_size(10, 0).with_targets(&[0, 3]);
+
+        dial.rotate_right(13);
+        dial.rotate_left(3);
+
+        assert_eq!(
+            dial.target_counts(),
+            [
+                TargetCount {
+                    target: 0,
+                    landings: 1,
+                    passes: 2,
+                },
+                TargetCount {
+                    target: 3,
+                    landings: 1,
+                    passes: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_works() {
+        let mut dial = Dial::with_size(10, 5).with_trace();
+
+        dial.apply(Rotation::Left(25));
+        dial.apply(Rotation::Right(3));
+
+        assert_eq!(
+            dial.trace().unwrap(),
+            [
+                TraceEntry {
+                    index: 1,
+                    rotation: Rotation::Left(25),
+                    pos_before: 5,
+                    pos_after: 0,
+                    zero_passes: 3,
+                },
+                TraceEntry {
+                    index: 2,
+                    rotation: Rotation::Right(3),
+                    pos_before: 0,
+                    pos_after: 3,
+                    zero_passes: 0,
+                },
+            ]
+        );
+        assert_eq!(
+            format_trace(dial.trace().unwrap(), TraceFormat::Csv),
+            "index,rotation,pos_before,pos_after,zero_passes\n1,L25,5,0,3\n2,R3,0,3,0\n"
+        );
+        assert_eq!(
+            format_trace(&dial.trace().unwrap()[1..], TraceFormat::Json),
+            r#"[{"index":2,"rotation":"R3","pos_before":0,"pos_after":3,"zero_passes":0}]
+"#
+        );
+        assert_eq!(Dial::new().trace(), None);
+    }
+
+    #[test]
+    fn undo_works() {
+        let mut dial = Dial::new().with_targets(&[0, 99]).with_trace();
+        dial.rotate_left(68);
+        dial.rotate_right(160);
+
+        dial.undo(Rotation::Right(160));
+        assert_eq!(dial.pos(), 82);
+        assert_eq!(dial.zero_count_1(), 0);
+        assert_eq!(dial.zero_count_2(), 1);
+        assert_eq!(dial.trace().unwrap().len(), 1);
+
+        dial.undo(Rotation::Left(68));
+        assert_eq!(dial.pos(), 50);
+        assert_eq!(dial.zero_count_2(), 0);
+        assert_eq!(
+            dial.target_counts(),
+            Dial::new().with_targets(&[0, 99]).target_counts()
+        );
+        assert_eq!(dial.trace(), Some(&[][..]));
+    }
+
+    #[test]
+    fn find_start_works() {
+        let rotations = [Rotation::Left(68), Rotation::Left(30), Rotation::Right(48)];
+
+        assert_eq!(find_start(100, 0, &rotations), 50);
+        assert_eq!(find_start(100, 99, &rotations), 49);
+        assert_eq!(find_start(1, 0, &rotations), 0);
+    }
+
+    #[test]
+    fn min_rotations_work() {
+        let dial = Dial::new();
+        assert_eq!(
+            dial.min_rotation_for_zero_count_1(0),
+            Some(Rotation::Right(0))
+        );
+        assert_eq!(
+            dial.min_rotation_for_zero_count_1(1),
+            Some(Rotation::Right(50))
+        );
+        assert_eq!(dial.min_rotation_for_zero_count_1(2), None);
+        assert_eq!(
+            dial.min_rotation_for_zero_count_2(3),
+            Some(Rotation::Right(250))
+        );
+
+        let mut dial = Dial::new();
+        dial.rotate_left(60);
+        assert_eq!(
+            dial.min_rotation_for_zero_count_1(1),
+            Some(Rotation::Right(10))
+        );
+        assert_eq!(dial.min_rotation_for_zero_count_2(0), None);
+        assert_eq!(
+            dial.min_rotation_for_zero_count_2(2),
+            Some(Rotation::Right(10))
+        );
+
+        let mut dial = Dial::new();
+        dial.rotate_left(50);
+        assert_eq!(
+            dial.min_rotation_for_zero_count_1(1),
+            Some(Rotation::Right(1))
+        );
+        assert_eq!(
+            dial.min_rotation_for_zero_count_1(2),
+            Some(Rotation::Right(0))
+        );
+        assert_eq!(Dial::with_size(1, 0).min_rotation_for_zero_count_1(0), None);
+        assert_eq!(
+            Dial::with_size(u64::MAX, 1).min_rotation_for_zero_count_2(3),
+            None
+        );
+    }
+
+    // Rotates the dial click by click and returns counts for the given targets.
+    fn count_targets_naively(
+        size: u64,
+        start: u64,
+        rotations: &[Rotation],
+        targets: &[u64],
+    ) -> Vec<TargetCount> {
+        let mut counts = targets
+            .iter()
+            .map(|&target| TargetCount {
+                target,
+                landings: 0,
+                passes: 0,
+            })
+            .collect::<Vec<_>>();
+
+        let mut pos = start;
+
+        for rotation in rotations {
+            let (n, step) = match *rotation {
+                Rotation::Left(n) => (n, size - 1),
+                Rotation::Right(n) => (n, 1),
+            };
+
+            for _ in 0..n {
+                pos = (pos + step) % size;
+
+                for count in counts.iter_mut().filter(|count| count.target == pos) {
+                    count.passes += 1;
+                }
+            }
+
+            for count in counts.iter_mut().filter(|count| count.target == pos) {
+                count.landings += 1;
+            }
+        }
+
+        counts
+    }
+
+    fn dial_and_rotations() -> impl Strategy<Value = (u64, u64, Vec<Rotation>)> {
+        let rotation = prop_oneof![
+            (0..300u64).prop_map(Rotation::Left),
+            (0..300u64).prop_map(Rotation::Right),
+        ];
+
+        (1..120u64).prop_flat_map(move |size| (Just(size), 0..size, vec(rotation.clone(), 0..30)))
+    }
+
+    proptest! {
+        #[test]
+        fn dial_matches_naive_rotation((size, start, rotations) in dial_and_rotations()) {
+            let input = Input {
+                dial: Dial::with_size(size, start),
+                rotations: rotations.clone(),
+            };
+            let dial = rotate_dial(&input);
+
+            prop_assert_eq!(
+                (dial.pos(), dial.zero_count_1(), dial.zero_count_2()),
+                rotate_naively(size, start, &rotations)
+            );
+        }
+
+        #[test]
+        fn undo_restores_dial(
+            (size, start, rotations) in dial_and_rotations(),
+            targets in vec(any::<u64>(), 0..5),
+        ) {
+            let targets = targets.iter().map(|target| target % size).collect::<Vec<_>>();
+
+            let mut dial = Dial::with_size(size, start).with_targets(&targets).with_trace();
+            let mut dials = vec![];
+
+            for &rotation in &rotations {
+                dials.push(dial.clone());
+                dial.apply(rotation);
+            }
+
+            for &rotation in rotations.iter().rev() {
+                dial.undo(rotation);
+
+                let expected = dials.pop().unwrap();
+                prop_assert_eq!(dial.pos(), expected.pos());
+                prop_assert_eq!(dial.zero_count_1(), expected.zero_count_1());
+                prop_assert_eq!(dial.zero_count_2(), expected.zero_count_2());
+                prop_assert_eq!(dial.target_counts(), expected.target_counts());
+                prop_assert_eq!(dial.trace(), expected.trace());
+            }
+        }
+
+        #[test]
+        fn find_start_matches_rotation((size, start, rotations) in dial_and_rotations()) {
+            let (end, _, _) = rotate_naively(size, start, &rotations);
+
+            prop_assert_eq!(find_start(size, end, &rotations), start);
+        }
+
+        #[test]
+        fn min_rotations_match_brute_force(
+            (size, start, rotations) in dial_and_rotations(),
+            extra in 0..4u64,
+        ) {
+            let input = Input {
+                dial: Dial::with_size(size, start),
+                rotations,
+            };
+            let dial = rotate_dial(&input);
+
+            // All rotations of up to 4 full turns, shortest first.
+            let candidates = (0..=4 * size)
+                .flat_map(|n| [Rotation::Right(n), Rotation::Left(n)])
+                .collect::<Vec<_>>();
+
+            let find = |count: fn(&Dial) -> u64, target: u64| {
+                candidates.iter().copied().find(|&rotation| {
+                    let mut dial = dial.clone();
+                    dial.apply(rotation);
+                    count(&dial) == target
+                })
+            };
+
+            let target_1 = dial.zero_count_1() + extra % 2;
+            let target_2 = dial.zero_count_2() + extra;
+
+            prop_assert_eq!(
+                dial.min_rotation_for_zero_count_1(target_1),
+                find(Dial::zero_count_1, target_1)
+            );
+            prop_assert_eq!(
+                dial.min_rotation_for_zero_count_2(target_2),
+                find(Dial::zero_count_2, target_2)
+            );
+        }
+
+        #[test]
+        fn target_counts_match_naive_rotation(
+            (size, start, rotations) in dial_and_rotations(),
+            targets in vec(any::<u64>(), 0..5),
+        ) {
+            let targets = targets.iter().map(|target| target % size).collect::<Vec<_>>();
+
+            let input = Input {
+                dial: Dial::with_size(size, start).with_targets(&targets),
+                rotations: rotations.clone(),
+            };
+            let dial = rotate_dial(&input);
+
+            prop_assert_eq!(
+                dial.target_counts(),
+                count_targets_naively(size, start, &rotations, &targets)
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Alternative solution to the day 1 puzzle. It uses the same algorithm as the
+// original solution, but the code is written to be as simple as possible.
+//
+// This is part of a small experiment to see how a simple solution feels like
+// and how it compares to a more structured solution (see `structured.rs`).
+
+use anyhow::{Result, bail, ensure};
+
+use crate::{Outcome, Rotation, TraceEntry};
+
+pub fn run(n: u64, start: u64, input: &str) -> Result<Outcome> {
+    let mut pos = start;
+    let mut count_1 = 0;
+    let mut count_2 = 0;
+    let mut trace = vec![];
+
+    // Use 128-bit arithmetic, so that the sums don't overflow even for the
+    // largest dials.
+    let size = n as u128;
+
+    for (i, line) in input.lines().enumerate() {
+        let pos_before = pos;
+        let count_2_before = count_2;
+
+        ensure!(!line.is_empty(), "rotation is empty");
+
+        let mut chars = line.chars();
+        let dir = chars.next().unwrap();
+        let dist = chars.as_str().parse::<u64>()?;
+
+        // The trick I use below is to convert a left rotation to a right one
+        // using a symmetry along the vertical axis of the dial, which can be
+        // expressed as:
+        //
+        //     pos <=> (N - pos) % N
+        //
+        // This approach simplifies the counting of zero crossings.
+        //
+        // There may be a simple way to count zero crossings for left rotations
+        // even without using the symmetry, but I don't see it at the moment.
+
+        match dir {
+            'L' => {
+                let raw_pos = ((n - pos) % n) as u128 + dist as u128;
+
+                pos = ((size - raw_pos % size) % size) as u64;
+                count_1 += if pos == 0 { 1 } else { 0 };
+                count_2 += (raw_pos / size) as u64;
+            }
+
+            'R' => {
+                let raw_pos = pos as u128 + dist as u128;
+
+                pos = (raw_pos % size) as u64;
+                count_1 += if pos == 0 { 1 } else { 0 };
+                count_2 += (raw_pos / size) as u64;
+            }
+
+            _ => bail!("invalid direction: {dir:?}"),
+        }
+
+        trace.push(TraceEntry {
+            index: i + 1,
+            rotation: if dir == 'L' {
+                Rotation::Left(dist)
+            } else {
+                Rotation::Right(dist)
+            },
+            pos_before,
+            pos_after: pos,
+            zero_passes: count_2 - count_2_before,
+        });
+    }
+
+    Ok(Outcome {
+        zero_count_1: count_1,
+        zero_count_2: count_2,
+        trace,
+    })
+}
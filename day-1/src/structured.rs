@@ -0,0 +1,130 @@
+// Alternative solution to the day 1 puzzle. It uses the same algorithm as the
+// original solution, but the code is written to be nicely structured.
+//
+// This is part of a small experiment to see how a structured solution feels
+// like and how it compares to a simpler solution (see `simple.rs`).
+
+use std::str::FromStr;
+
+use anyhow::{Error, Result, bail, ensure};
+
+use crate::{Outcome, TraceEntry};
+
+#[derive(Copy, Clone, Debug)]
+enum Rotation {
+    Left(u64),
+    Right(u64),
+}
+
+impl From<Rotation> for crate::Rotation {
+    fn from(rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::Left(dist) => crate::Rotation::Left(dist),
+            Rotation::Right(dist) => crate::Rotation::Right(dist),
+        }
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ensure!(!s.is_empty(), "rotation is empty");
+
+        let mut chars = s.chars();
+        let dir = chars.next().unwrap();
+        let dist = chars.as_str().parse::<u64>()?;
+
+        let rotation = match dir {
+            'L' => Rotation::Left(dist),
+            'R' => Rotation::Right(dist),
+            _ => bail!("invalid direction: {dir:?}"),
+        };
+
+        Ok(rotation)
+    }
+}
+
+struct Dial {
+    n: u64,
+    pos: u64,
+    count_1: u64,
+    count_2: u64,
+    trace: Vec<TraceEntry>,
+}
+
+impl Dial {
+    pub fn new(n: u64, start: u64) -> Self {
+        Self {
+            n,
+            pos: start,
+            count_1: 0,
+            count_2: 0,
+            trace: vec![],
+        }
+    }
+
+    pub fn rotate(&mut self, rotation: Rotation) {
+        // The trick I use below is to convert a left rotation to a right one
+        // using a symmetry along the vertical axis of the dial, which can be
+        // expressed as:
+        //
+        //     pos <=> (N - pos) % N
+        //
+        // This approach simplifies the counting of zero crossings.
+        //
+        // There may be a simple way to count zero crossings for left rotations
+        // even without using the symmetry, but I don't see it at the moment.
+
+        let pos_before = self.pos;
+        let count_2_before = self.count_2;
+
+        // Use 128-bit arithmetic, so that the sums don't overflow even for the
+        // largest dials.
+        let size = self.n as u128;
+
+        match rotation {
+            Rotation::Left(dist) => {
+                let raw_pos = ((self.n - self.pos) % self.n) as u128 + dist as u128;
+
+                self.pos = ((size - raw_pos % size) % size) as u64;
+                self.count_1 += if self.pos == 0 { 1 } else { 0 };
+                self.count_2 += (raw_pos / size) as u64;
+            }
+
+            Rotation::Right(dist) => {
+                let raw_pos = self.pos as u128 + dist as u128;
+
+                self.pos = (raw_pos % size) as u64;
+                self.count_1 += if self.pos == 0 { 1 } else { 0 };
+                self.count_2 += (raw_pos / size) as u64;
+            }
+        }
+
+        self.trace.push(TraceEntry {
+            index: self.trace.len() + 1,
+            rotation: rotation.into(),
+            pos_before,
+            pos_after: self.pos,
+            zero_passes: self.count_2 - count_2_before,
+        });
+    }
+
+    pub fn into_outcome(self) -> Outcome {
+        Outcome {
+            zero_count_1: self.count_1,
+            zero_count_2: self.count_2,
+            trace: self.trace,
+        }
+    }
+}
+
+pub fn run(n: u64, start: u64, input: &str) -> Result<Outcome> {
+    let mut dial = Dial::new(n, start);
+
+    for line in input.lines() {
+        dial.rotate(line.parse()?);
+    }
+
+    Ok(dial.into_outcome())
+}
@@ -0,0 +1,302 @@
+// There are three implementations of the solution: `Dial` (used by the `day-1`
+// binary and by `Day1`), `simple` and `structured`. They use the same algorithm,
+// but they are written independently, so running them side by side on the same
+// input is a cheap way to catch bugs in any of them.
+
+use std::panic;
+
+use anyhow::Result;
+
+use crate::{Dial, Outcome, TraceEntry, parse_line, simple, structured};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Variant {
+    Dial,
+    Simple,
+    Structured,
+}
+
+impl Variant {
+    /// All variants, starting with `Dial`, which is used as the reference.
+    pub const ALL: [Variant; 3] = [Variant::Dial, Variant::Simple, Variant::Structured];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Dial => "dial",
+            Variant::Simple => "simple",
+            Variant::Structured => "structured",
+        }
+    }
+
+    /// Rotates a dial of the given size, starting at the given position, by
+    /// the rotations in the input (one per line).
+    pub fn run(self, size: u64, start: u64, input: &str) -> Result<Outcome> {
+        match self {
+            Variant::Dial => run_dial(size, start, input),
+            Variant::Simple => simple::run(size, start, input),
+            Variant::Structured => structured::run(size, start, input),
+        }
+    }
+
+    /// Like `run`, but if the variant panics, returns the panic message as an
+    /// error instead, so that a crash can be reported like any other
+    /// difference between the variants.
+    pub fn run_catching_panics(
+        self,
+        size: u64,
+        start: u64,
+        input: &str,
+    ) -> Result<Result<Outcome>, String> {
+        panic::catch_unwind(|| self.run(size, start, input)).map_err(|payload| {
+            if let Some(message) = payload.downcast_ref::<&str>() {
+                message.to_string()
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.clone()
+            } else {
+                "unknown panic".to_owned()
+            }
+        })
+    }
+}
+
+fn run_dial(size: u64, start: u64, input: &str) -> Result<Outcome> {
+    let mut dial = Dial::with_size(size, start).with_trace();
+
+    for line in input.lines() {
+        dial.apply(parse_line(line)?);
+    }
+
+    Ok(Outcome {
+        zero_count_1: dial.zero_count_1(),
+        zero_count_2: dial.zero_count_2(),
+        trace: dial.trace().unwrap().to_vec(),
+    })
+}
+
+/// Describes the first difference between an outcome and the expected one, or
+/// returns `None` if they are the same. Errors count as the same even if their
+/// messages are different, as each variant words them differently.
+pub fn discrepancy(expected: &Result<Outcome>, actual: &Result<Outcome>) -> Option<String> {
+    let (expected, actual) = match (expected, actual) {
+        (Ok(expected), Ok(actual)) => (expected, actual),
+        (Err(_), Err(_)) => return None,
+        (Ok(_), Err(e)) => return Some(format!("unexpected error: {e:#}")),
+        (Err(e), Ok(_)) => return Some(format!("no error, expected: {e:#}")),
+    };
+
+    if let Some((i, (expected, actual))) = expected
+        .trace
+        .iter()
+        .zip(&actual.trace)
+        .enumerate()
+        .find(|(_, (expected, actual))| expected != actual)
+    {
+        return Some(format!(
+            "rotation {}: expected {}, got {}",
+            i + 1,
+            describe(expected),
+            describe(actual)
+        ));
+    }
+
+    if expected.trace.len() != actual.trace.len() {
+        return Some(format!(
+            "expected {} rotations, got {}",
+            expected.trace.len(),
+            actual.trace.len()
+        ));
+    }
+
+    if expected.zero_count_1 != actual.zero_count_1 {
+        return Some(format!(
+            "part 1: expected {}, got {}",
+            expected.zero_count_1, actual.zero_count_1
+        ));
+    }
+
+    if expected.zero_count_2 != actual.zero_count_2 {
+        return Some(format!(
+            "part 2: expected {}, got {}",
+            expected.zero_count_2, actual.zero_count_2
+        ));
+    }
+
+    None
+}
+
+fn describe(entry: &TraceEntry) -> String {
+    let TraceEntry {
+        rotation,
+        pos_before,
+        pos_after,
+        zero_passes,
+        ..
+    } = entry;
+
+    format!("{rotation} from {pos_before} to {pos_after} ({zero_passes} at 0)")
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::Rotation;
+
+    #[test]
+    fn variants_work() {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+        for variant in Variant::ALL {
+            let outcome = variant.run(100, 50, input).unwrap();
+
+            assert_eq!(
+                (outcome.zero_count_1, outcome.zero_count_2),
+                (3, 6),
+                "{}",
+                variant.name()
+            );
+            assert_eq!(outcome.trace.len(), 10, "{}", variant.name());
+        }
+    }
+
+    #[test]
+    fn variants_work_with_largest_dials() {
+        let max = u64::MAX;
+        let cases = [
+            (max, 10, format!("R{}\n", max - 5), (0, 1)),
+            (max, max - 1, format!("R1\nL{max}\nR{max}\n"), (3, 3)),
+            (max, 3, format!("L{}\n", max - 2), (0, 1)),
+        ];
+
+        for (size, start, input, counts) in cases {
+            for variant in Variant::ALL {
+                let outcome = variant.run(size, start, &input).unwrap();
+
+                assert_eq!(
+                    (outcome.zero_count_1, outcome.zero_count_2),
+                    counts,
+                    "{}: {input:?}",
+                    variant.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn run_catching_panics_works() {
+        assert!(matches!(
+            Variant::Dial.run_catching_panics(100, 50, "L68\n"),
+            Ok(Ok(_))
+        ));
+        assert!(matches!(
+            Variant::Dial.run_catching_panics(100, 50, "X\n"),
+            Ok(Err(_))
+        ));
+        assert!(Variant::Simple.run_catching_panics(0, 0, "L68\n").is_err());
+    }
+
+    #[test]
+    fn variants_reject_invalid_input() {
+        for input in ["\n", "X5\n", "L\n", "R-1\n", "L5\nR\n"] {
+            for variant in Variant::ALL {
+                assert!(
+                    variant.run(100, 50, input).is_err(),
+                    "{}: {input:?}",
+                    variant.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn discrepancy_works() {
+        let expected = Variant::Dial.run(100, 50, "L68\nR32\n");
+
+        let mut outcome = expected.as_ref().unwrap().clone();
+        assert_eq!(discrepancy(&expected, &Ok(outcome.clone())), None);
+
+        outcome.zero_count_2 = 3;
+        assert_eq!(
+            discrepancy(&expected, &Ok(outcome.clone())),
+            Some("part 2: expected 2, got 3".to_owned())
+        );
+
+        outcome.trace[1].pos_after = 1;
+        assert_eq!(
+            discrepancy(&expected, &Ok(outcome.clone())),
+            Some(
+                "rotation 2: expected R32 from 82 to 14 (1 at 0), \
+                 got R32 from 82 to 1 (1 at 0)"
+                    .to_owned()
+            )
+        );
+
+        outcome.trace.pop();
+        assert_eq!(
+            discrepancy(&expected, &Ok(outcome)),
+            Some("expected 2 rotations, got 1".to_owned())
+        );
+
+        let error = Variant::Dial.run(100, 50, "X\n");
+        assert_eq!(
+            discrepancy(&error, &Variant::Simple.run(100, 50, "X\n")),
+            None
+        );
+        assert!(discrepancy(&expected, &error).is_some());
+        assert!(discrepancy(&error, &expected).is_some());
+    }
+
+    fn rotations(dist: impl Strategy<Value = u64> + Clone) -> impl Strategy<Value = Vec<Rotation>> {
+        vec(
+            prop_oneof![
+                dist.clone().prop_map(Rotation::Left),
+                dist.prop_map(Rotation::Right),
+            ],
+            0..50,
+        )
+    }
+
+    fn dial_and_input() -> impl Strategy<Value = (u64, u64, String)> {
+        // Small dials are rotated by short distances, so that they often point
+        // at 0. Large ones (up to `u64::MAX` positions) are rotated by distances
+        // up to `u64::MAX`, so that positions and distances don't add up in u64.
+        let max = u64::MAX;
+        let small = (1..1000u64, rotations(0..10_000u64));
+        let large = (
+            prop_oneof![1 << 32..=max, max - 100..=max],
+            rotations(prop_oneof![any::<u64>(), max - 100..=max, 0..100u64]),
+        );
+
+        prop_oneof![small, large].prop_flat_map(|(size, rotations)| {
+            let input = rotations
+                .iter()
+                .map(|rotation| format!("{rotation}\n"))
+                .collect::<String>();
+
+            (Just(size), 0..size, Just(input))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn variants_agree((size, start, input) in dial_and_input()) {
+            let expected = Variant::Dial.run(size, start, &input);
+
+            for variant in Variant::ALL {
+                let outcome = variant.run_catching_panics(size, start, &input);
+
+                prop_assert!(
+                    matches!(outcome, Ok(Ok(_))),
+                    "{}: {:?}",
+                    variant.name(),
+                    outcome
+                );
+
+                let outcome = outcome.unwrap();
+                prop_assert_eq!(discrepancy(&expected, &outcome), None, "{}", variant.name());
+            }
+        }
+    }
+}
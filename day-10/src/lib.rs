@@ -0,0 +1,283 @@
+use std::collections::VecDeque;
+
+use anyhow::{Error, Result, anyhow};
+use input::{Line, ParseError};
+use microlp::{OptimizationDirection, Problem};
+use solver::Solver;
+
+// I chose to represent indicator light configurations as well as button wirings
+// as bit sets backed by `usize`. This means that in part 1:
+//
+//   1. Pressing a button can be expressed as XORing an indicator light
+//      configuration by the button wiring.
+//
+//   2. The mapping from an indicator light configuration to a minimum number of
+//      button presses required for it can be implemented using a simple vector
+//      indexed by the indicator light configuration.
+//
+// The representation limits the number of indicator lights to `usize::BITS`,
+// but this is not a problem as the machines have at most 10 indicator lights.
+
+const INITIAL_LIGHTS: usize = 0;
+
+#[derive(Clone, Debug)]
+pub struct Machine {
+    size: usize,
+    lights: usize,
+    wirings: Vec<usize>,
+    joltages: Vec<u32>,
+}
+
+pub fn find_min_presses_1(machine: &Machine) -> Option<u32> {
+    let mut min_presses = vec![u32::MAX; 1 << machine.size];
+    let mut queue = VecDeque::new();
+
+    min_presses[INITIAL_LIGHTS] = 0;
+    queue.push_back(INITIAL_LIGHTS);
+
+    while !queue.is_empty() {
+        let lights = queue.pop_front().unwrap();
+        let presses = min_presses[lights];
+
+        if lights == machine.lights {
+            return Some(presses);
+        }
+
+        let new_presses = presses + 1;
+
+        for &wiring in &machine.wirings {
+            let new_lights = lights ^ wiring;
+
+            if min_presses[new_lights] <= new_presses {
+                continue;
+            }
+
+            min_presses[new_lights] = new_presses;
+            queue.push_back(new_lights);
+        }
+    }
+
+    None
+}
+
+pub fn find_min_presses_2(machine: &Machine) -> Option<u32> {
+    let mut problem = Problem::new(OptimizationDirection::Minimize);
+
+    let vars = (0..machine.wirings.len())
+        .map(|_| problem.add_integer_var(1.0, (0, i32::MAX)))
+        .collect::<Vec<_>>();
+
+    for i in 0..machine.size {
+        let expr = machine
+            .wirings
+            .iter()
+            .zip(&vars)
+            .filter_map(|(&wiring, &var)| {
+                if wiring & (1 << i) != 0 {
+                    Some((var, 1.0))
+                } else {
+                    None
+                }
+            });
+
+        problem.add_constraint(expr, microlp::ComparisonOp::Eq, machine.joltages[i] as f64);
+    }
+
+    let Ok(solution) = problem.solve() else {
+        return None;
+    };
+
+    Some(solution.objective().round() as u32)
+}
+
+// Returns the part without its opening and closing delimiters.
+fn strip_delimiters<'a>(
+    line: &Line,
+    part: &'a str,
+    open: char,
+    close: char,
+    what: &str,
+) -> Result<&'a str, ParseError> {
+    part.strip_prefix(open)
+        .and_then(|part| part.strip_suffix(close))
+        .ok_or_else(|| line.error_at(part, format!("invalid {what}: {part:?}")))
+}
+
+fn parse_machines(lines: &[Line]) -> Result<Vec<Machine>, ParseError> {
+    lines.iter().map(parse_machine).collect()
+}
+
+fn parse_machine(line: &Line) -> Result<Machine, ParseError> {
+    let parts = line.text.split_whitespace().collect::<Vec<_>>();
+
+    if parts.len() < 3 {
+        return Err(line.error("machine description has invalid format"));
+    }
+
+    let (size, lights) = parse_lights(line, parts[0])?;
+    let wirings = parts[1..parts.len() - 1]
+        .iter()
+        .map(|part| parse_wiring(line, part, size))
+        .collect::<Result<Vec<_>, _>>()?;
+    let joltages = parse_joltages(line, parts[parts.len() - 1])?;
+
+    if joltages.len() != size {
+        return Err(line.error_at(
+            parts[parts.len() - 1],
+            "indicator light diagram and joltage requirements have a different size",
+        ));
+    }
+
+    let machine = Machine {
+        size,
+        lights,
+        wirings,
+        joltages,
+    };
+
+    Ok(machine)
+}
+
+fn parse_lights(line: &Line, part: &str) -> Result<(usize, usize), ParseError> {
+    let diagram = strip_delimiters(line, part, '[', ']', "indicator light diagram")?;
+    let size = diagram.chars().count();
+
+    if size == 0 || size > usize::BITS as usize {
+        return Err(line.error_at(part, "indicator light diagram has invalid size"));
+    }
+
+    let mut lights = 0;
+
+    for (i, (j, ch)) in diagram.char_indices().enumerate() {
+        match ch {
+            '.' => (),
+            '#' => lights |= 1 << i,
+            _ => {
+                let light = &diagram[j..j + ch.len_utf8()];
+
+                return Err(line.error_at(light, format!("invalid indicator light: {ch:?}")));
+            }
+        }
+    }
+
+    Ok((size, lights))
+}
+
+fn parse_wiring(line: &Line, part: &str, size: usize) -> Result<usize, ParseError> {
+    let indices = strip_delimiters(line, part, '(', ')', "button wiring schematic")?;
+    let mut wiring = 0;
+
+    for index in indices.split(',') {
+        let i = line.parse_part::<usize>(index, "indicator light index")?;
+
+        if i >= size {
+            return Err(line.error_at(index, format!("indicator light index out of range: {i}")));
+        }
+
+        wiring |= 1 << i;
+    }
+
+    Ok(wiring)
+}
+
+fn parse_joltages(line: &Line, part: &str) -> Result<Vec<u32>, ParseError> {
+    strip_delimiters(line, part, '{', '}', "joltage requirements")?
+        .split(',')
+        .map(|joltage| line.parse_part(joltage, "joltage requirement"))
+        .collect()
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<Machine>;
+    type Options = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = Error;
+
+    fn parse(input: &str, _: &()) -> Result<Vec<Machine>> {
+        let lines = input::lines(input).collect::<Vec<_>>();
+
+        Ok(parse_machines(&lines)?)
+    }
+
+    fn part_1(machines: &Vec<Machine>) -> Result<u32> {
+        let min_presses = machines
+            .iter()
+            .map(find_min_presses_1)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| anyhow!("indicator lights can't be configured on some machines"))?;
+
+        Ok(min_presses.iter().sum())
+    }
+
+    fn part_2(machines: &Vec<Machine>) -> Result<u32> {
+        let min_presses = machines
+            .iter()
+            .map(find_min_presses_2)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                anyhow!("joltage level counters can't be configured on some machines")
+            })?;
+
+        Ok(min_presses.iter().sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_machine_works() {
+        let line = Line {
+            number: 1,
+            text: "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}",
+        };
+        let machine = parse_machine(&line).unwrap();
+
+        assert_eq!(machine.size, 4);
+        assert_eq!(machine.lights, 0b0110);
+        assert_eq!(
+            machine.wirings,
+            vec![0b1000, 0b1010, 0b0100, 0b1100, 0b0101, 0b0011]
+        );
+        assert_eq!(machine.joltages, vec![3, 5, 4, 7]);
+
+        for (text, column, message) in [
+            (
+                "[.##.] {3,5,4,7}",
+                1,
+                "machine description has invalid format",
+            ),
+            ("[.#x.] (3) {3,5,4,7}", 4, "invalid indicator light: 'x'"),
+            (
+                "[.##.] (3 {3,5,4,7}",
+                8,
+                "invalid button wiring schematic: \"(3\"",
+            ),
+            (
+                "[.##.] (1,4) {3,5,4,7}",
+                11,
+                "indicator light index out of range: 4",
+            ),
+            (
+                "[.##.] (3) {3,5,x,7}",
+                17,
+                "invalid joltage requirement: \"x\"",
+            ),
+            (
+                "[.##.] (3) {3,5,4}",
+                12,
+                "indicator light diagram and joltage requirements have a different size",
+            ),
+        ] {
+            let line = Line { number: 1, text };
+            let error = parse_machine(&line).unwrap_err();
+
+            assert_eq!(error.column(), column, "{text:?}");
+            assert_eq!(error.message(), message, "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::iter::Sum;
+use std::ops::Add;
+
+use anyhow::{Error, Result};
+use solver::Solver;
+
+const DEVICE_OUT: &str = "out";
+const DEVICE_DAC: &str = "dac";
+const DEVICE_FFT: &str = "fft";
+
+// The code below could be generalized to handle any number of "via" devices,
+// but this wasn't needed to solve the puzzle, so I didn't do it.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Counts {
+    pub via_none: u64,
+    pub via_dac: u64,
+    pub via_fft: u64,
+    pub via_dac_and_fft: u64,
+}
+
+impl Counts {
+    pub fn zero() -> Self {
+        Self {
+            via_none: 0,
+            via_dac: 0,
+            via_fft: 0,
+            via_dac_and_fft: 0,
+        }
+    }
+
+    pub fn out() -> Self {
+        Self {
+            via_none: 1,
+            via_dac: 0,
+            via_fft: 0,
+            via_dac_and_fft: 0,
+        }
+    }
+
+    pub fn with_dac(self) -> Self {
+        Self {
+            via_none: 0,
+            via_dac: self.via_none,
+            via_fft: 0,
+            via_dac_and_fft: self.via_fft,
+        }
+    }
+
+    pub fn with_fft(self) -> Self {
+        Self {
+            via_none: 0,
+            via_dac: 0,
+            via_fft: self.via_none,
+            via_dac_and_fft: self.via_dac,
+        }
+    }
+}
+
+impl Add for Counts {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Counts {
+            via_none: self.via_none + rhs.via_none,
+            via_dac: self.via_dac + rhs.via_dac,
+            via_fft: self.via_fft + rhs.via_fft,
+            via_dac_and_fft: self.via_dac_and_fft + rhs.via_dac_and_fft,
+        }
+    }
+}
+
+impl Sum for Counts {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Counts::zero(), |a, b| a + b)
+    }
+}
+
+pub fn count_paths(devices: &HashMap<String, Vec<String>>, start: &str) -> Counts {
+    do_count_paths(devices, &mut HashMap::new(), start)
+}
+
+fn do_count_paths(
+    devices: &HashMap<String, Vec<String>>,
+    memo: &mut HashMap<String, Counts>,
+    start: &str,
+) -> Counts {
+    let Some(outputs) = devices.get(start) else {
+        return Counts::zero();
+    };
+
+    if let Some(&counts) = memo.get(start) {
+        return counts;
+    }
+
+    let mut counts = outputs
+        .iter()
+        .map(|output| {
+            if output == DEVICE_OUT {
+                return Counts::out();
+            }
+
+            do_count_paths(devices, memo, output)
+        })
+        .sum::<Counts>();
+
+    if start == DEVICE_DAC {
+        counts = counts.with_dac();
+    }
+
+    if start == DEVICE_FFT {
+        counts = counts.with_fft();
+    }
+
+    memo.insert(start.to_owned(), counts);
+    counts
+}
+
+fn parse_devices(input: &str) -> Result<HashMap<String, Vec<String>>> {
+    let mut devices = HashMap::new();
+
+    for line in input::lines(input) {
+        let (label, outputs) = input::labeled_list(&line)?;
+
+        if outputs.is_empty() {
+            return Err(line.error("device doesn't have any outputs").into());
+        }
+
+        devices.insert(label.to_owned(), outputs);
+    }
+
+    Ok(devices)
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = HashMap<String, Vec<String>>;
+    type Options = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+    type Error = Error;
+
+    fn parse(input: &str, _: &()) -> Result<HashMap<String, Vec<String>>> {
+        parse_devices(input)
+    }
+
+    fn part_1(devices: &HashMap<String, Vec<String>>) -> Result<u64> {
+        let counts = count_paths(devices, "you");
+
+        Ok(counts.via_none + counts.via_dac + counts.via_fft + counts.via_dac_and_fft)
+    }
+
+    fn part_2(devices: &HashMap<String, Vec<String>>) -> Result<u64> {
+        let counts = count_paths(devices, "svr");
+
+        Ok(counts.via_dac_and_fft)
+    }
+}
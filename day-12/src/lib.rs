@@ -0,0 +1,240 @@
+use anyhow::{Error, Result, bail, ensure};
+use grid::Grid;
+use input::{Line, ParseError};
+use solver::Solver;
+
+#[derive(Clone, Debug)]
+pub struct Shape {
+    pub cells: Grid<bool>,
+}
+
+impl Shape {
+    pub fn width(&self) -> u32 {
+        self.cells.width() as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        self.cells.height() as u32
+    }
+
+    pub fn area(&self) -> u32 {
+        self.cells.iter().filter(|&&filled| filled).count() as u32
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Region {
+    pub width: u32,
+    pub height: u32,
+    pub quantities: Vec<u32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Problem {
+    pub shapes: Vec<Shape>,
+    pub shape_width: u32,
+    pub shape_height: u32,
+    pub regions: Vec<Region>,
+}
+
+impl Region {
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    pub fn total_quantity(&self) -> u32 {
+        self.quantities.iter().sum()
+    }
+}
+
+// Returns the index of a shape if the line is a shape index (`N:`).
+fn shape_index<'a>(line: &Line<'a>) -> Option<&'a str> {
+    line.text
+        .strip_suffix(':')
+        .filter(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn parse_region(line: &Line, shape_count: usize) -> Result<Region, ParseError> {
+    let (dims, quantities) = input::labeled_list(line)?;
+
+    let (width, height) = dims
+        .split_once('x')
+        .ok_or_else(|| line.error_at(dims, format!("invalid region size: {dims:?}")))?;
+
+    let width = line.parse_part(width, "region width")?;
+    let height = line.parse_part(height, "region height")?;
+
+    if quantities.len() != shape_count {
+        return Err(line.error(format!(
+            "region quantities don't correspond to {shape_count} shapes"
+        )));
+    }
+
+    Ok(Region {
+        width,
+        height,
+        quantities,
+    })
+}
+
+fn parse_problem(input: &str) -> Result<Problem> {
+    let mut shapes = vec![];
+    let mut regions = vec![];
+
+    let mut lines = input::lines(input).peekable();
+
+    while let Some(line) = lines.next_if(|line| shape_index(line).is_some()) {
+        let index = shape_index(&line).unwrap();
+
+        if line.parse_part::<usize>(index, "shape index")? != shapes.len() {
+            return Err(line
+                .error_at(index, format!("expected shape index {}", shapes.len()))
+                .into());
+        }
+
+        let mut rows = vec![];
+
+        while let Some(line) = lines.next_if(|line| !line.text.is_empty()) {
+            rows.push(line);
+        }
+
+        if rows.is_empty() {
+            return Err(line.error("shape doesn't have any rows").into());
+        }
+
+        let cells = Grid::parse_lines(&rows, |ch| match ch {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })?;
+
+        shapes.push(Shape { cells });
+
+        while let Some(line) = lines.peek()
+            && line.text.is_empty()
+        {
+            lines.next();
+        }
+    }
+
+    ensure!(!shapes.is_empty(), "problem doesn't have any shapes");
+
+    let shape_widths = shapes.iter().map(Shape::width).collect::<Vec<_>>();
+    let shape_heights = shapes.iter().map(Shape::height).collect::<Vec<_>>();
+
+    ensure!(
+        shape_widths.windows(2).all(|width| width[0] == width[1]),
+        "shapes don't have the same width"
+    );
+    ensure!(
+        shape_heights
+            .windows(2)
+            .all(|height| height[0] == height[1]),
+        "shapes don't have the same height"
+    );
+
+    let shape_width = shape_widths[0];
+    let shape_height = shape_heights[0];
+
+    for line in lines {
+        regions.push(parse_region(&line, shapes.len())?);
+    }
+
+    let problem = Problem {
+        shapes,
+        shape_width,
+        shape_height,
+        regions,
+    };
+
+    Ok(problem)
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Problem;
+    type Options = ();
+    type Answer1 = u32;
+    type Answer2 = ();
+    type Error = Error;
+
+    fn parse(input: &str, _: &()) -> Result<Problem> {
+        parse_problem(input)
+    }
+
+    fn part_1(problem: &Problem) -> Result<u32> {
+        let mut count = 0;
+        for region in &problem.regions {
+            let shapes_total_area = problem
+                .shapes
+                .iter()
+                .zip(&region.quantities)
+                .map(|(shape, quantity)| shape.area() * quantity)
+                .sum::<u32>();
+
+            if shapes_total_area > region.area() {
+                continue;
+            }
+
+            let simple_quantity =
+                (region.width / problem.shape_width) * (region.height / problem.shape_height);
+            if simple_quantity >= region.total_quantity() {
+                count += 1;
+            } else {
+                bail!("problem is NP-complete");
+            }
+        }
+
+        Ok(count)
+    }
+
+    fn part_2(_: &Problem) -> Result<()> {
+        // There is no part 2 of the day 12 puzzle.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_point_to_lines() {
+        let shapes = "0:\n##\n#.\n\n1:\n.#\n##\n\n";
+
+        let problem = parse_problem(&format!("{shapes}4x4: 1 2\n")).unwrap();
+        assert_eq!(problem.regions[0].quantities, vec![1, 2]);
+
+        for (regions, line, column, message) in [
+            (
+                "4x4: 1 2\n4y4: 1 2\n",
+                10,
+                1,
+                "invalid region size: \"4y4\"",
+            ),
+            ("4x4: 1 x\n", 9, 8, "invalid value: \"x\""),
+            (
+                "4x4: 1\n",
+                9,
+                1,
+                "region quantities don't correspond to 2 shapes",
+            ),
+        ] {
+            let error = parse_problem(&format!("{shapes}{regions}")).unwrap_err();
+            let error = error.downcast::<ParseError>().unwrap();
+
+            assert_eq!(
+                (error.line(), error.column()),
+                (line, column),
+                "{regions:?}"
+            );
+            assert_eq!(error.message(), message, "{regions:?}");
+        }
+
+        let error = parse_problem("0:\n##\n\n2:\n##\n").unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+        assert_eq!((error.line(), error.column()), (4, 1));
+        assert_eq!(error.message(), "expected shape index 1");
+    }
+}
@@ -0,0 +1,987 @@
+use std::ops::RangeInclusive;
+use std::vec::Vec;
+use std::{mem, thread};
+
+use anyhow::{Result, bail};
+use input::ParseError;
+use interval_set::IntervalSet;
+use solver::{Options, Solver, option_value};
+
+/// Rule deciding which IDs are invalid, based on the digits of the ID.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Rule {
+    /// The ID is a pattern repeated exactly the given number of times (the rule
+    /// of part 1 is `Exactly(2)`).
+    Exactly(u32),
+    /// The ID is a pattern repeated at least the given number of times (the
+    /// rule of part 2 is `AtLeast(2)`).
+    AtLeast(u32),
+    /// The ID reads the same backwards (which is true for all IDs with one
+    /// digit).
+    Palindromic,
+}
+
+impl Rule {
+    /// Checks the ID written in the given base against the rule. Zero is never
+    /// invalid, as it isn't an ID.
+    pub fn is_invalid_id(self, id: u64, base: u32) -> bool {
+        if id == 0 {
+            return false;
+        }
+
+        let digits = digits(id, base);
+        let len = digits.len() as u32;
+
+        let repeats = |times: u32| {
+            let n = (len / times) as usize;
+
+            len.is_multiple_of(times) && digits.chunks(n).all(|chunk| chunk == &digits[..n])
+        };
+
+        match self {
+            Rule::Exactly(0) => false,
+            Rule::Exactly(times) => repeats(times),
+            Rule::AtLeast(times) => (times.max(1)..=len).any(repeats),
+            Rule::Palindromic => digits.iter().eq(digits.iter().rev()),
+        }
+    }
+
+    // Returns the numbers of times a pattern can be repeated to get an invalid
+    // ID with `len` digits. If a pattern can be repeated `a` times and `b` times
+    // where `b` is a multiple of `a`, only `a` is returned, as a pattern repeated
+    // `b` times is also a longer pattern repeated `a` times.
+    fn repetitions(self, len: u32) -> Vec<u32> {
+        match self {
+            Rule::Exactly(times) => {
+                if times > 0 && len.is_multiple_of(times) {
+                    vec![times]
+                } else {
+                    vec![]
+                }
+            }
+
+            Rule::AtLeast(times) => {
+                let all = (times.max(1)..=len)
+                    .filter(|&n| len.is_multiple_of(n))
+                    .collect::<Vec<_>>();
+
+                all.iter()
+                    .copied()
+                    .filter(|&n| all.iter().all(|&m| m == n || !n.is_multiple_of(m)))
+                    .collect()
+            }
+
+            Rule::Palindromic => unreachable!("palindromes aren't repetitions"),
+        }
+    }
+}
+
+// Invalid IDs are generated instead of checking every ID in the range. An ID
+// with `len` digits consisting of a pattern repeated `n` times is equal to the
+// pattern multiplied by a number like 101 (for `len` 4 and `n` 2) or 10101 (for
+// `len` 6 and `n` 3), i.e. `(base^len - 1) / (base^(len / n) - 1)`. So the
+// invalid IDs of a given length are the multiples of such numbers among the IDs
+// of that length.
+//
+// Counting and summing the invalid IDs doesn't even need them to be generated.
+// The multiples of a factor `f` in a range are `f * first..=f * last`, so there
+// are `last - first + 1` of them, and their sum is `f` times the sum of the
+// arithmetic series `first..=last`. This takes time proportional only to the
+// number of ID lengths in the range (see `total` for IDs consisting of
+// a pattern repeated in more than one way).
+//
+// Palindromes are generated from their first halves instead.
+
+/// Returns the invalid IDs in the range, with digits in the given base.
+///
+/// Panics if the base isn't between 2 and 36.
+pub fn find_invalid_ids(range: RangeInclusive<u64>, rule: Rule, base: u32) -> Vec<u64> {
+    let mut ids = vec![];
+
+    for (len, range) in id_lengths(range, base) {
+        if rule == Rule::Palindromic {
+            ids.extend(palindromes(len, range, base));
+            continue;
+        }
+
+        // An ID can consist of a pattern repeated more than one of the numbers
+        // of times (like 111111, which is 111 repeated twice and 11 repeated
+        // three times), so to generate each ID only once, IDs generated for one
+        // number of times are skipped if they are also generated for an earlier
+        // one.
+        let factors = rule
+            .repetitions(len)
+            .into_iter()
+            .map(|n| repetition_factor(len, n, base))
+            .collect::<Vec<_>>();
+
+        let start = ids.len();
+
+        for (i, &factor) in factors.iter().enumerate() {
+            ids.extend(
+                multiples(range.clone(), factor)
+                    .filter(|id| factors[..i].iter().all(|f| !id.is_multiple_of(*f))),
+            );
+        }
+
+        ids[start..].sort_unstable();
+    }
+
+    ids
+}
+
+/// Returns the number of invalid IDs in the range without generating them (the
+/// count of palindromes is computed from the range of their first halves).
+///
+/// Panics if the base isn't between 2 and 36.
+pub fn count_invalid_ids(range: RangeInclusive<u64>, rule: Rule, base: u32) -> u64 {
+    if rule == Rule::Palindromic {
+        return id_lengths(range, base)
+            .map(|(len, range)| {
+                let halves = palindrome_halves(len, range, base);
+
+                (halves.end() + 1).saturating_sub(*halves.start())
+            })
+            .sum();
+    }
+
+    total(range, rule, base, |first, last, _| {
+        (last - first + 1) as u128
+    }) as u64
+}
+
+/// Returns the sum of invalid IDs in the range without generating them (except
+/// for palindromes).
+///
+/// Palindromes are generated and added up one by one, so the time this takes
+/// grows with their number (about the square root of the range's end). Summing
+/// them in a range like `1..=u64::MAX` doesn't finish in any reasonable time.
+///
+/// Panics if the base isn't between 2 and 36.
+pub fn sum_invalid_ids(range: RangeInclusive<u64>, rule: Rule, base: u32) -> u128 {
+    // There's no simple formula for the sum of palindromes, so they are added up
+    // one by one.
+    if rule == Rule::Palindromic {
+        return id_lengths(range, base)
+            .flat_map(|(len, range)| palindromes(len, range, base))
+            .map(u128::from)
+            .sum();
+    }
+
+    // The IDs are the factor multiplied by `first..=last`, so their sum is the
+    // factor multiplied by the sum of an arithmetic series. The division by 2 is
+    // done first, so that the product doesn't overflow for the largest ranges.
+    total(range, rule, base, |first, last, factor| {
+        let (first, last) = (first as u128, last as u128);
+        let count = last - first + 1;
+
+        let series_sum = if count.is_multiple_of(2) {
+            count / 2 * (first + last)
+        } else {
+            (first + last) / 2 * count
+        };
+
+        series_sum * factor as u128
+    })
+}
+
+// Adds up the given totals of the multiples of the repetition factors in the
+// range. The multiples are given to the total as the range of numbers the factor
+// is multiplied by. IDs that are multiples of more than one factor are handled
+// using inclusion–exclusion: an ID consisting of a pattern repeated both `a` and
+// `b` times also consists of a pattern repeated `lcm(a, b)` times.
+fn total(
+    range: RangeInclusive<u64>,
+    rule: Rule,
+    base: u32,
+    total: impl Fn(u64, u64, u64) -> u128,
+) -> u128 {
+    let mut added = 0;
+    let mut subtracted = 0;
+
+    for (len, range) in id_lengths(range, base) {
+        let repetitions = rule.repetitions(len);
+
+        for subset in 1..1u32 << repetitions.len() {
+            let n = (0..repetitions.len())
+                .filter(|i| subset & 1 << i != 0)
+                .fold(1, |n, i| lcm(n, repetitions[i]));
+
+            let factor = repetition_factor(len, n, base);
+            let (first, last) = (range.start().div_ceil(factor), range.end() / factor);
+
+            if first > last {
+                continue;
+            }
+
+            if subset.count_ones() % 2 == 1 {
+                added += total(first, last, factor);
+            } else {
+                subtracted += total(first, last, factor);
+            }
+        }
+    }
+
+    added - subtracted
+}
+
+pub fn find_invalid_ids_1(range: RangeInclusive<u64>) -> Vec<u64> {
+    find_invalid_ids(range, Rule::Exactly(2), 10)
+}
+
+pub fn is_invalid_id_1(id: u64) -> bool {
+    let id_string = id.to_string();
+    if !id_string.len().is_multiple_of(2) {
+        return false;
+    }
+
+    let part_1 = &id_string[..id_string.len() / 2];
+    let part_2 = &id_string[id_string.len() / 2..];
+
+    part_1 == part_2
+}
+
+pub fn find_invalid_ids_2(range: RangeInclusive<u64>) -> Vec<u64> {
+    find_invalid_ids(range, Rule::AtLeast(2), 10)
+}
+
+pub fn is_invalid_id_2(id: u64) -> bool {
+    let id_string = id.to_string();
+
+    (1..=id_string.len() / 2).any(|i| is_repeated(&id_string, i))
+}
+
+fn is_repeated(id_string: &str, n: usize) -> bool {
+    if !id_string.len().is_multiple_of(n) {
+        return false;
+    }
+
+    let chunks = (0..id_string.len() / n)
+        .map(|i| &id_string[i * n..(i + 1) * n])
+        .collect::<Vec<_>>();
+
+    chunks.windows(2).all(|window| window[0] == window[1])
+}
+
+// Returns the digits of the number in the given base, starting from the most
+// significant one.
+fn digits(mut n: u64, base: u32) -> Vec<u32> {
+    assert!((2..=36).contains(&base), "invalid base: {base}");
+
+    let mut digits = vec![];
+
+    while n > 0 {
+        digits.push((n % base as u64) as u32);
+        n /= base as u64;
+    }
+
+    digits.reverse();
+    digits
+}
+
+// Splits the range into the parts containing IDs with the same number of
+// digits, and returns them together with the number. Zero is skipped.
+fn id_lengths(
+    range: RangeInclusive<u64>,
+    base: u32,
+) -> impl Iterator<Item = (u32, RangeInclusive<u64>)> {
+    assert!((2..=36).contains(&base), "invalid base: {base}");
+
+    // 128-bit arithmetic is used, as e.g. 10^20 doesn't fit into `u64`.
+    let (start, end) = (*range.start() as u128, *range.end() as u128);
+    let base = base as u128;
+
+    (1..=u64::MAX.ilog(base as u64) + 1).filter_map(move |len| {
+        let start = start.max(base.pow(len - 1));
+        let end = end.min(base.pow(len) - 1);
+
+        (start <= end).then_some((len, start as u64..=end as u64))
+    })
+}
+
+// Returns the number that a pattern needs to be multiplied by to be repeated
+// `n` times, so that the result has `len` digits.
+fn repetition_factor(len: u32, n: u32, base: u32) -> u64 {
+    let base = base as u128;
+
+    ((base.pow(len) - 1) / (base.pow(len / n) - 1)) as u64
+}
+
+fn multiples(range: RangeInclusive<u64>, factor: u64) -> impl Iterator<Item = u64> {
+    (range.start().div_ceil(factor)..=range.end() / factor).map(move |k| k * factor)
+}
+
+fn lcm(a: u32, b: u32) -> u32 {
+    let gcd = |mut a: u32, mut b: u32| {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+
+        a
+    };
+
+    a / gcd(a, b) * b
+}
+
+// Returns the palindromes with `len` digits in the range (which must contain
+// only numbers with `len` digits).
+fn palindromes(len: u32, range: RangeInclusive<u64>, base: u32) -> impl Iterator<Item = u64> {
+    palindrome_halves(len, range, base).map(move |half| palindrome(half, len, base) as u64)
+}
+
+// A palindrome is given by its first `(len + 1) / 2` digits, which this function
+// calls its half. Palindromes are ordered in the same way as their halves, so
+// the palindromes in the range are given by a range of halves.
+fn palindrome_halves(len: u32, range: RangeInclusive<u64>, base: u32) -> RangeInclusive<u64> {
+    let shift = (base as u64).pow(len / 2);
+
+    let mut first = range.start() / shift;
+    if palindrome(first, len, base) < *range.start() as u128 {
+        first += 1;
+    }
+
+    let mut last = range.end() / shift;
+    if palindrome(last, len, base) > *range.end() as u128 {
+        last -= 1;
+    }
+
+    first..=last
+}
+
+// Returns the palindrome with the given half. 128-bit arithmetic is used, as the
+// palindrome can be larger than the largest ID.
+fn palindrome(half: u64, len: u32, base: u32) -> u128 {
+    let base = base as u128;
+
+    let mut palindrome = half as u128;
+    let mut mirrored = if len.is_multiple_of(2) {
+        half as u128
+    } else {
+        half as u128 / base
+    };
+
+    for _ in 0..len / 2 {
+        palindrome = palindrome * base + mirrored % base;
+        mirrored /= base;
+    }
+
+    palindrome
+}
+
+/// Parses comma-separated ID ranges written in the given base (on one or more
+/// lines).
+pub fn parse_input(input: &str, base: u32) -> Result<Vec<RangeInclusive<u64>>, ParseError> {
+    let mut ranges = vec![];
+
+    for line in input::lines(input).filter(|line| !line.text.trim().is_empty()) {
+        for range in line.text.trim().split(',') {
+            let (start, end) = range
+                .split_once('-')
+                .ok_or_else(|| line.error_at(range, format!("invalid range: {range:?}")))?;
+
+            let start = u64::from_str_radix(start, base)
+                .map_err(|_| line.error_at(start, format!("invalid ID: {start:?}")))?;
+
+            let end = u64::from_str_radix(end, base)
+                .map_err(|_| line.error_at(end, format!("invalid ID: {end:?}")))?;
+
+            if start > end {
+                return Err(line.error_at(range, format!("range is reversed: {range:?}")));
+            }
+
+            ranges.push(start..=end);
+        }
+    }
+
+    Ok(ranges)
+}
+
+/// Merges overlapping (and adjacent) ranges, so that IDs in more than one range
+/// are counted only once. The merged ranges are sorted.
+pub fn merge_ranges(ranges: &[RangeInclusive<u64>]) -> Vec<RangeInclusive<u64>> {
+    let merged = ranges.iter().cloned().collect::<IntervalSet<_>>();
+
+    merged.ranges().to_vec()
+}
+
+/// Rules of both parts, the base the IDs are written in (both in the input and
+/// when checking their digits), whether overlapping ranges are merged, and the
+/// number of threads the ranges are split between.
+///
+/// Splitting the ranges between threads only speeds up rules whose IDs are added
+/// up one by one (`Rule::Palindromic`). The sums for the other rules are computed
+/// in closed form, so they are instant anyway.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct IdOptions {
+    pub rule_1: Rule,
+    pub rule_2: Rule,
+    pub base: u32,
+    pub merge: bool,
+    pub jobs: usize,
+}
+
+impl Default for IdOptions {
+    fn default() -> Self {
+        IdOptions {
+            rule_1: Rule::Exactly(2),
+            rule_2: Rule::AtLeast(2),
+            base: 10,
+            merge: true,
+            jobs: 1,
+        }
+    }
+}
+
+impl Options for IdOptions {
+    const USAGE: &'static str = "[--rule-1 <exactly:<K>|at-least:<K>|palindromic>] \
+                                 [--rule-2 <exactly:<K>|at-least:<K>|palindromic>] \
+                                 [--base <2-36>] [--no-merge] [--jobs <N>]";
+
+    fn parse_option(&mut self, option: &str, args: &mut dyn Iterator<Item = &str>) -> Result<bool> {
+        match option {
+            "--rule-1" => self.rule_1 = parse_rule(option_value(args, option)?)?,
+            "--rule-2" => self.rule_2 = parse_rule(option_value(args, option)?)?,
+            "--base" => {
+                let value = option_value(args, option)?;
+
+                self.base = match value.parse() {
+                    Ok(base) if (2..=36).contains(&base) => base,
+                    _ => bail!("invalid base: {value:?}"),
+                };
+            }
+            "--no-merge" => self.merge = false,
+            "--jobs" => {
+                let value = option_value(args, option)?;
+
+                self.jobs = match value.parse() {
+                    Ok(jobs) if jobs > 0 => jobs,
+                    _ => bail!("invalid number of jobs: {value:?}"),
+                };
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+}
+
+fn parse_rule(s: &str) -> Result<Rule> {
+    if s == "palindromic" {
+        return Ok(Rule::Palindromic);
+    }
+
+    let (rule, times): (fn(u32) -> Rule, _) = if let Some(times) = s.strip_prefix("exactly:") {
+        (Rule::Exactly, times)
+    } else if let Some(times) = s.strip_prefix("at-least:") {
+        (Rule::AtLeast, times)
+    } else {
+        bail!("invalid rule: {s:?}");
+    };
+
+    match times.parse() {
+        Ok(times) if times > 0 => Ok(rule(times)),
+        _ => bail!("invalid number of repetitions: {times:?}"),
+    }
+}
+
+/// Ranges of IDs, together with the options they are checked with.
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub ranges: Vec<RangeInclusive<u64>>,
+    pub options: IdOptions,
+}
+
+/// Splits the ranges into at most `n` parts with about the same number of IDs.
+/// Ranges are split between parts if needed, so the parts may be unbalanced
+/// only if there are fewer IDs than parts.
+pub fn split_ranges(ranges: &[RangeInclusive<u64>], n: usize) -> Vec<Vec<RangeInclusive<u64>>> {
+    assert!(n > 0);
+
+    // 128-bit arithmetic is used, as the IDs in all ranges may not fit into
+    // `u64` (even the IDs in one range don't if it contains all of them).
+    let range_len = |start: u64, end: u64| (end - start) as u128 + 1;
+
+    let total = ranges
+        .iter()
+        .map(|range| range_len(*range.start(), *range.end()))
+        .sum::<u128>();
+    let part_len = total.div_ceil(n as u128);
+
+    let mut parts = vec![];
+    let mut part = vec![];
+    let mut len = 0;
+
+    for range in ranges {
+        let mut start = *range.start();
+
+        loop {
+            let taken = range_len(start, *range.end()).min(part_len - len);
+            let end = start + (taken - 1) as u64;
+
+            part.push(start..=end);
+            len += taken;
+
+            if len == part_len {
+                parts.push(mem::take(&mut part));
+                len = 0;
+            }
+
+            if end == *range.end() {
+                break;
+            }
+
+            start = end + 1;
+        }
+    }
+
+    if !part.is_empty() {
+        parts.push(part);
+    }
+
+    parts
+}
+
+fn sum_all_invalid_ids(input: &Input, rule: Rule) -> u128 {
+    let IdOptions { base, jobs, .. } = input.options;
+
+    let sum = |ranges: &[RangeInclusive<u64>]| {
+        ranges
+            .iter()
+            .map(|range| sum_invalid_ids(range.clone(), rule, base))
+            .sum::<u128>()
+    };
+
+    if jobs == 1 {
+        return sum(&input.ranges);
+    }
+
+    // Each part is summed by its own thread. The sums are added up in the order
+    // of the parts, so that the result doesn't depend on which thread finishes
+    // first.
+    let parts = split_ranges(&input.ranges, jobs);
+
+    thread::scope(|scope| {
+        parts
+            .iter()
+            .map(|part| scope.spawn(move || sum(part)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .sum()
+    })
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Input;
+    type Options = IdOptions;
+    type Answer1 = u128;
+    type Answer2 = u128;
+    type Error = ParseError;
+
+    fn parse(input: &str, options: &IdOptions) -> Result<Input, ParseError> {
+        let mut ranges = parse_input(input, options.base)?;
+
+        if options.merge {
+            ranges = merge_ranges(&ranges);
+        }
+
+        Ok(Input {
+            ranges,
+            options: *options,
+        })
+    }
+
+    fn part_1(input: &Input) -> Result<u128, ParseError> {
+        Ok(sum_all_invalid_ids(input, input.options.rule_1))
+    }
+
+    fn part_2(input: &Input) -> Result<u128, ParseError> {
+        Ok(sum_all_invalid_ids(input, input.options.rule_2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_invalid_ids_1_works() {
+        assert_eq!(find_invalid_ids_1(11..=22), vec![11, 22]);
+        assert_eq!(find_invalid_ids_1(95..=115), vec![99]);
+        assert_eq!(find_invalid_ids_1(998..=1012), vec![1010]);
+        assert_eq!(
+            find_invalid_ids_1(1188511880..=1188511890),
+            vec![1188511885]
+        );
+        assert_eq!(find_invalid_ids_1(222220..=222224), vec![222222]);
+        assert_eq!(find_invalid_ids_1(1698522..=1698528), vec![]);
+        assert_eq!(find_invalid_ids_1(446443..=446449), vec![446446]);
+        assert_eq!(find_invalid_ids_1(38593856..=38593862), vec![38593859]);
+        assert_eq!(find_invalid_ids_1(565653..=565659), vec![]);
+        assert_eq!(find_invalid_ids_1(824824821..=824824827), vec![]);
+        assert_eq!(find_invalid_ids_1(2121212118..=2121212124), vec![]);
+    }
+
+    #[test]
+    fn find_invalid_ids_1_works_for_large_ranges() {
+        // For each even length, there are 9 * 10^(length / 2 - 1) patterns.
+        assert_eq!(find_invalid_ids_1(1..=999_999_999_999).len(), 999_999);
+        assert_eq!(
+            find_invalid_ids_1(u64::MAX - 10_000_000_000..=u64::MAX),
+            vec![18446744071844674407]
+        );
+    }
+
+    #[test]
+    fn find_invalid_ids_2_works() {
+        assert_eq!(find_invalid_ids_2(11..=22), vec![11, 22]);
+        assert_eq!(find_invalid_ids_2(95..=115), vec![99, 111]);
+        assert_eq!(find_invalid_ids_2(998..=1012), vec![999, 1010]);
+        assert_eq!(
+            find_invalid_ids_2(1188511880..=1188511890),
+            vec![1188511885]
+        );
+        assert_eq!(find_invalid_ids_2(222220..=222224), vec![222222]);
+        assert_eq!(find_invalid_ids_2(1698522..=1698528), vec![]);
+        assert_eq!(find_invalid_ids_2(446443..=446449), vec![446446]);
+        assert_eq!(find_invalid_ids_2(38593856..=38593862), vec![38593859]);
+        assert_eq!(find_invalid_ids_2(565653..=565659), vec![565656]);
+        assert_eq!(find_invalid_ids_2(824824821..=824824827), vec![824824824]);
+        assert_eq!(
+            find_invalid_ids_2(2121212118..=2121212124),
+            vec![2121212121]
+        );
+    }
+
+    #[test]
+    fn generated_ids_match_brute_force() {
+        let ranges = [
+            0..=200_000,
+            999_990_000..=1_000_010_000,
+            111_111_000_000..=111_111_200_000,
+            u64::MAX - 100_000..=u64::MAX,
+        ];
+
+        for range in ranges {
+            assert_eq!(
+                find_invalid_ids_1(range.clone()),
+                range
+                    .clone()
+                    .filter(|&id| is_invalid_id_1(id))
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                find_invalid_ids_2(range.clone()),
+                range
+                    .clone()
+                    .filter(|&id| is_invalid_id_2(id))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    const RULES: [Rule; 9] = [
+        Rule::Exactly(0),
+        Rule::Exactly(1),
+        Rule::Exactly(2),
+        Rule::Exactly(3),
+        Rule::AtLeast(1),
+        Rule::AtLeast(2),
+        Rule::AtLeast(3),
+        Rule::AtLeast(4),
+        Rule::Palindromic,
+    ];
+
+    const BASES: [u32; 4] = [2, 10, 16, 36];
+
+    #[test]
+    fn find_invalid_ids_works() {
+        assert_eq!(
+            find_invalid_ids(100..=1000, Rule::Exactly(3), 10),
+            vec![111, 222, 333, 444, 555, 666, 777, 888, 999]
+        );
+        assert_eq!(
+            find_invalid_ids(100_000..=111_111, Rule::Exactly(3), 10),
+            vec![101010, 111111]
+        );
+        assert_eq!(
+            find_invalid_ids(100_000..=111_111, Rule::AtLeast(3), 10),
+            vec![101010, 111111]
+        );
+        assert_eq!(
+            find_invalid_ids(100_000..=111_111, Rule::AtLeast(4), 10),
+            vec![111111]
+        );
+        assert_eq!(
+            find_invalid_ids(990..=1111, Rule::Palindromic, 10),
+            vec![999, 1001, 1111]
+        );
+        assert_eq!(
+            find_invalid_ids(1..=15, Rule::Exactly(2), 2),
+            vec![0b11, 0b1010, 0b1111]
+        );
+        assert_eq!(
+            find_invalid_ids(0x100..=0x1000, Rule::Palindromic, 16).len(),
+            15 * 16
+        );
+
+        for base in BASES {
+            for rule in RULES {
+                for range in [0..=20_000, 111_111_100_000..=111_111_110_000] {
+                    assert_eq!(
+                        find_invalid_ids(range.clone(), rule, base),
+                        range
+                            .clone()
+                            .filter(|&id| rule.is_invalid_id(id, base))
+                            .collect::<Vec<_>>(),
+                        "{rule:?} in base {base}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn count_and_sum_invalid_ids_work() {
+        let ranges = [
+            0..=0,
+            1..=100_000,
+            999_990_000..=1_000_010_000,
+            111_111_000_000..=111_111_200_000,
+            u64::MAX - 100_000..=u64::MAX,
+        ];
+
+        for base in BASES {
+            for rule in RULES {
+                for range in ranges.clone() {
+                    let ids = find_invalid_ids(range.clone(), rule, base);
+
+                    assert_eq!(
+                        count_invalid_ids(range.clone(), rule, base),
+                        ids.len() as u64,
+                        "{rule:?} in base {base}"
+                    );
+                    assert_eq!(
+                        sum_invalid_ids(range.clone(), rule, base),
+                        ids.iter().map(|&id| id as u128).sum(),
+                        "{rule:?} in base {base}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn count_and_sum_invalid_ids_work_for_large_ranges() {
+        // Every ID is a pattern repeated once.
+        let max = u64::MAX as u128;
+        assert_eq!(
+            count_invalid_ids(0..=u64::MAX, Rule::Exactly(1), 10),
+            u64::MAX
+        );
+        assert_eq!(
+            sum_invalid_ids(0..=u64::MAX, Rule::Exactly(1), 10),
+            max * (max + 1) / 2
+        );
+
+        assert_eq!(
+            count_invalid_ids(1..=999_999_999_999, Rule::Exactly(2), 10),
+            999_999
+        );
+        assert_eq!(
+            count_invalid_ids(1..=999_999_999_999, Rule::Palindromic, 10),
+            1_999_998
+        );
+        // In base 2, there are 2^31 palindromes with 64 digits.
+        assert_eq!(
+            count_invalid_ids(1 << 63..=u64::MAX, Rule::Palindromic, 2),
+            1 << 31
+        );
+
+        for rule in [Rule::Exactly(2), Rule::AtLeast(2), Rule::Palindromic] {
+            assert_eq!(
+                sum_invalid_ids(1..=9_999_999_999, rule, 10),
+                find_invalid_ids(1..=9_999_999_999, rule, 10)
+                    .iter()
+                    .map(|&id| id as u128)
+                    .sum()
+            );
+        }
+
+        assert!(sum_invalid_ids(0..=u64::MAX, Rule::AtLeast(2), 10) > max);
+    }
+
+    #[test]
+    fn parse_input_works() {
+        assert_eq!(
+            parse_input("11-22,95-115\n", 10).unwrap(),
+            vec![11..=22, 95..=115]
+        );
+        assert_eq!(
+            parse_input("11-22\n95-115\n\n", 10).unwrap(),
+            vec![11..=22, 95..=115]
+        );
+
+        for (input, line, column, message) in [
+            ("11-22,115-95", 1, 7, "range is reversed: \"115-95\""),
+            ("11-22,95", 1, 7, "invalid range: \"95\""),
+            ("11-22\n95-1x5", 2, 4, "invalid ID: \"1x5\""),
+        ] {
+            let error = parse_input(input, 10).unwrap_err();
+
+            assert_eq!((error.line(), error.column()), (line, column), "{input:?}");
+            assert_eq!(error.message(), message, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn merge_ranges_works() {
+        assert_eq!(
+            merge_ranges(&[95..=115, 11..=22, 100..=200, 11..=22, 201..=201]),
+            vec![11..=22, 95..=201]
+        );
+
+        let input = "11-22,95-115,11-22,20-99";
+        let merged = Day2::parse(input, &IdOptions::default()).unwrap();
+        let raw = Day2::parse(
+            input,
+            &IdOptions {
+                merge: false,
+                ..IdOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(merged.ranges, vec![11..=115]);
+        assert_eq!(
+            Day2::part_1(&merged).unwrap(),
+            11 + 22 + 33 + 44 + 55 + 66 + 77 + 88 + 99
+        );
+        assert_eq!(
+            Day2::part_1(&raw).unwrap(),
+            2 * (11 + 22) + 22 + 33 + 44 + 55 + 66 + 77 + 88 + 99 + 99
+        );
+    }
+
+    #[test]
+    fn id_options_work() {
+        let mut options = IdOptions::default();
+        let mut args = ["palindromic", "at-least:3", "16", "exactly:4"].into_iter();
+
+        assert!(options.parse_option("--rule-1", &mut args).unwrap());
+        assert!(options.parse_option("--rule-2", &mut args).unwrap());
+        assert!(options.parse_option("--base", &mut args).unwrap());
+        assert_eq!(
+            options,
+            IdOptions {
+                rule_1: Rule::Palindromic,
+                rule_2: Rule::AtLeast(3),
+                base: 16,
+                merge: true,
+                jobs: 1,
+            }
+        );
+
+        assert!(options.parse_option("--rule-2", &mut args).unwrap());
+        assert_eq!(options.rule_2, Rule::Exactly(4));
+
+        assert!(parse_rule("exactly:0").is_err());
+        assert!(parse_rule("twice").is_err());
+        assert!(
+            options
+                .parse_option("--base", &mut ["1"].into_iter())
+                .is_err()
+        );
+        assert!(
+            options
+                .parse_option("--base", &mut ["37"].into_iter())
+                .is_err()
+        );
+        assert!(options.parse_option("--no-merge", &mut args).unwrap());
+        assert!(!options.merge);
+        assert!(
+            options
+                .parse_option("--jobs", &mut ["4"].into_iter())
+                .unwrap()
+        );
+        assert_eq!(options.jobs, 4);
+        assert!(
+            options
+                .parse_option("--jobs", &mut ["0"].into_iter())
+                .is_err()
+        );
+        assert!(!options.parse_option("--unknown", &mut args).unwrap());
+
+        let input = Day2::parse("10-ff,1a-1b\n", &options).unwrap();
+        assert_eq!(input.ranges, vec![0x10..=0xff, 0x1a..=0x1b]);
+        assert_eq!(
+            Day2::part_1(&input).unwrap(),
+            (1..=15).map(|digit| digit * 0x11).sum::<u128>()
+        );
+    }
+
+    #[test]
+    fn split_ranges_works() {
+        assert_eq!(
+            split_ranges(&[1..=10, 21..=25], 3),
+            vec![vec![1..=5], vec![6..=10], vec![21..=25]]
+        );
+        assert_eq!(
+            split_ranges(&[1..=2, 5..=5], 2),
+            vec![vec![1..=2], vec![5..=5]]
+        );
+        assert_eq!(
+            split_ranges(&[1..=2, 5..=5], 5),
+            vec![vec![1..=1], vec![2..=2], vec![5..=5]]
+        );
+        assert_eq!(
+            split_ranges(&[0..=u64::MAX, 0..=u64::MAX], 2),
+            vec![vec![0..=u64::MAX], vec![0..=u64::MAX]]
+        );
+        assert_eq!(split_ranges(&[], 4), Vec::<Vec<_>>::new());
+    }
+
+    #[test]
+    fn jobs_dont_change_results() {
+        let inputs = [
+            "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,\
+             446443-446449,38593856-38593862,565653-565659,824824821-824824827,\
+             2121212118-2121212124",
+            "1-1000000,500000-2000000,1-1,123456789-987654321,18446744073709551000-18446744073709551615",
+        ];
+
+        for input in inputs {
+            for merge in [true, false] {
+                for (rule_1, rule_2) in [
+                    (Rule::Exactly(2), Rule::AtLeast(2)),
+                    (Rule::Palindromic, Rule::Exactly(3)),
+                ] {
+                    let options = IdOptions {
+                        rule_1,
+                        rule_2,
+                        merge,
+                        ..IdOptions::default()
+                    };
+
+                    let expected = Day2::parse(input, &options).unwrap();
+                    let expected = (
+                        Day2::part_1(&expected).unwrap(),
+                        Day2::part_2(&expected).unwrap(),
+                    );
+
+                    for jobs in [2, 3, 8, 100] {
+                        let input = Day2::parse(input, &IdOptions { jobs, ..options }).unwrap();
+
+                        assert_eq!(
+                            (Day2::part_1(&input).unwrap(), Day2::part_2(&input).unwrap()),
+                            expected,
+                            "{jobs} jobs"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,741 @@
+use std::mem;
+
+use anyhow::{Error, Result, anyhow, bail};
+use input::{Line, ParseError};
+use solver::{Options, Solver, option_value};
+
+/// Maximum number of batteries whose joltage always fits into `u128`.
+pub const MAX_BATTERIES: usize = 38;
+
+/// Batteries selected from a bank, given by their indices (in increasing
+/// order), together with their joltage.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Selection {
+    pub indices: Vec<usize>,
+    pub value: u128,
+}
+
+/// Selects `n` batteries with the maximum joltage from the bank.
+///
+/// Panics if the bank has fewer than `n` batteries or if `n` is greater than
+/// `MAX_BATTERIES`.
+pub fn select_max_batteries(bank: &[u8], n: usize) -> Selection {
+    swap_batteries(bank, n, Goal::Max)
+}
+
+fn swap_batteries(bank: &[u8], n: usize, goal: Goal) -> Selection {
+    assert!(n <= bank.len(), "bank has fewer than {n} batteries");
+    assert!(
+        n <= MAX_BATTERIES,
+        "joltage of {n} batteries may not fit into u128"
+    );
+
+    // First, we select the last `n` batteries. Then we go through the rest of
+    // the batteries right-to-left and try to increase (or decrease) the total
+    // joltage by adjusting the selection.
+
+    let mut selected = (bank.len() - n..bank.len()).collect::<Vec<_>>();
+
+    for i in (0..bank.len() - n).rev() {
+        let mut b = i;
+
+        for s in selected.iter_mut() {
+            let swap = match goal {
+                Goal::Max => bank[b] >= bank[*s],
+                Goal::Min => bank[b] <= bank[*s],
+            };
+
+            if swap {
+                mem::swap(&mut b, s);
+            } else {
+                break;
+            }
+        }
+    }
+
+    let value = selected
+        .iter()
+        .fold(0, |acc, &i| 10 * acc + (bank[i] - b'0') as u128);
+
+    Selection {
+        indices: selected,
+        value,
+    }
+}
+
+pub fn find_max_joltage(bank: &[u8], n: usize) -> u64 {
+    select_max_batteries(bank, n)
+        .value
+        .try_into()
+        .expect("joltage doesn't fit into u64")
+}
+
+/// Whether to select batteries with the maximum or the minimum joltage.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Goal {
+    Max,
+    Min,
+}
+
+/// At most `max` batteries can be selected from any `len` consecutive ones.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Window {
+    pub max: usize,
+    pub len: usize,
+}
+
+/// Constraints on the selected batteries.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct Constraints {
+    /// The first selected battery can't have joltage 0 (this matters only when
+    /// selecting batteries with the minimum joltage).
+    pub no_leading_zero: bool,
+    pub window: Option<Window>,
+    /// Indices of batteries that can't be selected.
+    pub forbidden: Vec<usize>,
+}
+
+/// Selects `n` batteries with the minimum joltage from the bank.
+///
+/// Panics under the same conditions as `select_max_batteries`.
+pub fn select_min_batteries(bank: &[u8], n: usize) -> Selection {
+    swap_batteries(bank, n, Goal::Min)
+}
+
+/// Selects `n` batteries with the maximum or the minimum joltage from the bank,
+/// or returns `None` if there aren't `n` batteries satisfying the constraints.
+///
+/// Panics if `n` is greater than `MAX_BATTERIES`.
+pub fn select_batteries(
+    bank: &[u8],
+    n: usize,
+    goal: Goal,
+    constraints: &Constraints,
+) -> Option<Selection> {
+    assert!(
+        n <= MAX_BATTERIES,
+        "joltage of {n} batteries may not fit into u128"
+    );
+
+    // Without constraints, the swap loop is used, as it's much faster than the
+    // search below.
+    if *constraints == Constraints::default() {
+        return (n <= bank.len()).then(|| swap_batteries(bank, n, goal));
+    }
+
+    let mut allowed = vec![true; bank.len()];
+
+    for &i in &constraints.forbidden {
+        if let Some(allowed) = allowed.get_mut(i) {
+            *allowed = false;
+        }
+    }
+
+    let is_better = |a: u8, b: u8| match goal {
+        Goal::Max => a > b,
+        Goal::Min => a < b,
+    };
+
+    // The batteries are selected one by one, from the most significant digit,
+    // choosing the best battery after which the remaining batteries can still
+    // be selected. If there is more than one, the leftmost one is chosen, as it
+    // leaves the most options for the rest of the selection.
+
+    let mut indices = vec![];
+
+    for k in 0..n {
+        let start = indices.last().map_or(0, |&i| i + 1);
+
+        let best = (start..bank.len())
+            .filter(|&i| allowed[i])
+            .filter(|&i| !(k == 0 && constraints.no_leading_zero && bank[i] == b'0'))
+            .filter(|&i| {
+                let mut indices = indices.clone();
+
+                fits_window(&indices, i, constraints.window) && {
+                    indices.push(i);
+                    fill(&mut indices, &allowed, constraints.window, n) == n
+                }
+            })
+            .reduce(|best, i| {
+                if is_better(bank[i], bank[best]) {
+                    i
+                } else {
+                    best
+                }
+            })?;
+
+        indices.push(best);
+    }
+
+    let value = indices
+        .iter()
+        .fold(0, |acc, &i| 10 * acc + (bank[i] - b'0') as u128);
+
+    Some(Selection { indices, value })
+}
+
+// Checks whether the battery can be selected after the given ones (which must
+// be to the left of it) without breaking the window constraint.
+fn fits_window(indices: &[usize], i: usize, window: Option<Window>) -> bool {
+    let Some(Window { max, len }) = window else {
+        return true;
+    };
+
+    // As the other batteries are to the left, the window ending at the battery
+    // contains the most of them.
+    let in_window = indices.iter().rev().take_while(|&&j| j + len > i).count();
+
+    in_window < max
+}
+
+// Selects the leftmost batteries that can be selected after the given ones
+// until there are `n` of them, and returns how many there are. Selecting the
+// leftmost batteries maximizes their number.
+fn fill(indices: &mut Vec<usize>, allowed: &[bool], window: Option<Window>, n: usize) -> usize {
+    let start = indices.last().map_or(0, |&i| i + 1);
+
+    for (i, &allowed) in allowed.iter().enumerate().skip(start) {
+        if indices.len() == n {
+            break;
+        }
+
+        if allowed && fits_window(indices, i, window) {
+            indices.push(i);
+        }
+    }
+
+    indices.len()
+}
+
+fn parse_bank(line: &Line, min_len: usize) -> Result<Vec<u8>, ParseError> {
+    if let Some((i, ch)) = line
+        .text
+        .char_indices()
+        .find(|(_, ch)| !ch.is_ascii_digit())
+    {
+        let part = &line.text[i..i + ch.len_utf8()];
+
+        return Err(line.error_at(part, format!("invalid battery: {ch:?}")));
+    }
+
+    if line.text.len() < min_len {
+        return Err(line.error(format!("bank has fewer than {min_len} batteries")));
+    }
+
+    Ok(line.text.as_bytes().to_vec())
+}
+
+/// Numbers of batteries selected from each bank in both parts, and how they
+/// are selected.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BatteryOptions {
+    pub batteries_1: usize,
+    pub batteries_2: usize,
+    pub goal: Goal,
+    pub constraints: Constraints,
+    /// Whether a bank too short for either part is an error. Otherwise, it's
+    /// skipped in the part it's too short for.
+    pub strict: bool,
+}
+
+impl Default for BatteryOptions {
+    fn default() -> Self {
+        BatteryOptions {
+            batteries_1: 2,
+            batteries_2: 12,
+            goal: Goal::Max,
+            constraints: Constraints::default(),
+            strict: false,
+        }
+    }
+}
+
+impl Options for BatteryOptions {
+    const USAGE: &'static str = "[--batteries-1 <N>] [--batteries-2 <N>] [--min] \
+                                 [--no-leading-zero] [--window <M>/<W>] [--forbid <I>,<J>,...] \
+                                 [--strict]";
+
+    fn parse_option(&mut self, option: &str, args: &mut dyn Iterator<Item = &str>) -> Result<bool> {
+        let batteries = match option {
+            "--batteries-1" => &mut self.batteries_1,
+            "--batteries-2" => &mut self.batteries_2,
+
+            "--min" => {
+                self.goal = Goal::Min;
+                return Ok(true);
+            }
+
+            "--strict" => {
+                self.strict = true;
+                return Ok(true);
+            }
+
+            "--no-leading-zero" => {
+                self.constraints.no_leading_zero = true;
+                return Ok(true);
+            }
+
+            "--window" => {
+                let value = option_value(args, option)?;
+                self.constraints.window = Some(parse_window(value)?);
+                return Ok(true);
+            }
+
+            "--forbid" => {
+                let value = option_value(args, option)?;
+                self.constraints.forbidden = value
+                    .split(',')
+                    .map(|i| {
+                        i.parse()
+                            .map_err(|_| anyhow!("invalid battery index: {i:?}"))
+                    })
+                    .collect::<Result<_>>()?;
+                return Ok(true);
+            }
+
+            _ => return Ok(false),
+        };
+
+        let value = option_value(args, option)?;
+
+        *batteries = match value.parse() {
+            Ok(n) if (1..=MAX_BATTERIES).contains(&n) => n,
+            _ => bail!("invalid number of batteries: {value:?}"),
+        };
+
+        Ok(true)
+    }
+}
+
+fn parse_window(s: &str) -> Result<Window> {
+    let window = s.split_once('/').and_then(|(max, len)| {
+        let max = max.parse().ok()?;
+        let len = len.parse().ok()?;
+
+        (max > 0 && len > 0).then_some(Window { max, len })
+    });
+
+    window.ok_or_else(|| anyhow!("invalid window: {s:?}"))
+}
+
+/// Banks of batteries, together with the numbers of batteries to select.
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub banks: Vec<Vec<u8>>,
+    pub options: BatteryOptions,
+}
+
+/// Returns numbers of lines with banks that have fewer than `n` batteries, which
+/// are skipped in the part selecting `n` batteries.
+pub fn skipped_lines(banks: &[Vec<u8>], n: usize) -> Vec<usize> {
+    (1..)
+        .zip(banks)
+        .filter(|(_, bank)| bank.len() < n)
+        .map(|(line, _)| line)
+        .collect()
+}
+
+fn total_joltage(banks: &[Vec<u8>], n: usize, options: &BatteryOptions) -> Result<u128> {
+    let BatteryOptions {
+        goal, constraints, ..
+    } = options;
+
+    banks
+        .iter()
+        .enumerate()
+        .filter(|(_, bank)| bank.len() >= n)
+        .try_fold(0u128, |total, (i, bank)| {
+            let selection = select_batteries(bank, n, *goal, constraints)
+                .ok_or_else(|| anyhow!("can't select {n} batteries from bank {}", i + 1))?;
+
+            total
+                .checked_add(selection.value)
+                .ok_or_else(|| anyhow!("total joltage doesn't fit into u128"))
+        })
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = Input;
+    type Options = BatteryOptions;
+    type Answer1 = u128;
+    type Answer2 = u128;
+    type Error = Error;
+
+    fn parse(input: &str, options: &BatteryOptions) -> Result<Input> {
+        let min_len = if options.strict {
+            options.batteries_1.max(options.batteries_2)
+        } else {
+            0
+        };
+        let banks = input::lines(input)
+            .map(|line| parse_bank(&line, min_len))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Input {
+            banks,
+            options: options.clone(),
+        })
+    }
+
+    fn part_1(input: &Input) -> Result<u128> {
+        total_joltage(&input.banks, input.options.batteries_1, &input.options)
+    }
+
+    fn part_2(input: &Input) -> Result<u128> {
+        total_joltage(&input.banks, input.options.batteries_2, &input.options)
+    }
+
+    fn warnings(input: &Input) -> Vec<String> {
+        let Input { banks, options } = input;
+
+        [(1, options.batteries_1), (2, options.batteries_2)]
+            .into_iter()
+            .filter_map(|(part, n)| {
+                let lines = skipped_lines(banks, n);
+
+                (!lines.is_empty()).then(|| {
+                    let lines = lines.iter().map(usize::to_string).collect::<Vec<_>>();
+
+                    format!(
+                        "part {part}: skipped {} bank(s) with fewer than {n} batteries on line(s) {}",
+                        lines.len(),
+                        lines.join(", ")
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn find_max_joltage_works() {
+        assert_eq!(find_max_joltage(b"987654321111111", 2), 98);
+        assert_eq!(find_max_joltage(b"811111111111119", 2), 89);
+        assert_eq!(find_max_joltage(b"234234234234278", 2), 78);
+        assert_eq!(find_max_joltage(b"818181911112111", 2), 92);
+
+        assert_eq!(find_max_joltage(b"987654321111111", 12), 987654321111);
+        assert_eq!(find_max_joltage(b"811111111111119", 12), 811111111119);
+        assert_eq!(find_max_joltage(b"234234234234278", 12), 434234234278);
+        assert_eq!(find_max_joltage(b"818181911112111", 12), 888911112111);
+    }
+
+    #[test]
+    fn select_max_batteries_works() {
+        assert_eq!(
+            select_max_batteries(b"818181911112111", 2),
+            Selection {
+                indices: vec![6, 11],
+                value: 92,
+            }
+        );
+        assert_eq!(
+            select_max_batteries(b"234234234234278", 12),
+            Selection {
+                indices: vec![2, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+                value: 434234234278,
+            }
+        );
+        assert_eq!(select_max_batteries(b"5", 0).indices, vec![]);
+
+        let bank = [b'9'; MAX_BATTERIES];
+        assert_eq!(
+            select_max_batteries(&bank, MAX_BATTERIES).value,
+            10u128.pow(MAX_BATTERIES as u32) - 1
+        );
+    }
+
+    #[test]
+    fn battery_options_work() {
+        let mut options = BatteryOptions::default();
+        let mut args = ["3", "20"].into_iter();
+
+        assert!(options.parse_option("--batteries-1", &mut args).unwrap());
+        assert!(options.parse_option("--batteries-2", &mut args).unwrap());
+        assert_eq!(
+            options,
+            BatteryOptions {
+                batteries_1: 3,
+                batteries_2: 20,
+                ..BatteryOptions::default()
+            }
+        );
+
+        assert!(
+            options
+                .parse_option("--batteries-1", &mut ["0"].into_iter())
+                .is_err()
+        );
+        assert!(
+            options
+                .parse_option("--batteries-2", &mut ["39"].into_iter())
+                .is_err()
+        );
+        assert!(!options.parse_option("--unknown", &mut args).unwrap());
+
+        let input =
+            Day3::parse("987654321111111111111\n818181911112111999999\n", &options).unwrap();
+        assert_eq!(Day3::part_1(&input).unwrap(), 987 + 999);
+        assert_eq!(
+            Day3::part_2(&input).unwrap(),
+            98765432111111111111 + 88181911112111999999
+        );
+
+        let input = Day3::parse("987654321111111\n", &options).unwrap();
+        assert_eq!(Day3::part_2(&input).unwrap(), 0);
+
+        options.strict = true;
+        assert!(Day3::parse("987654321111111\n", &options).is_err());
+    }
+
+    #[test]
+    fn short_banks_are_skipped() {
+        let input = "987654321111111\n81\n\n234234234234278\n5\n";
+
+        let banks = Day3::parse(input, &BatteryOptions::default()).unwrap();
+        assert_eq!(Day3::part_1(&banks).unwrap(), 98 + 81 + 78);
+        assert_eq!(Day3::part_2(&banks).unwrap(), 987654321111 + 434234234278);
+        assert_eq!(skipped_lines(&banks.banks, 2), vec![3, 5]);
+        assert_eq!(
+            Day3::warnings(&banks),
+            vec![
+                "part 1: skipped 2 bank(s) with fewer than 2 batteries on line(s) 3, 5",
+                "part 2: skipped 3 bank(s) with fewer than 12 batteries on line(s) 2, 3, 5",
+            ]
+        );
+
+        let banks = Day3::parse("987654321111111\n", &BatteryOptions::default()).unwrap();
+        assert!(Day3::warnings(&banks).is_empty());
+
+        let mut options = BatteryOptions::default();
+        assert!(
+            options
+                .parse_option("--strict", &mut [].into_iter())
+                .unwrap()
+        );
+        assert!(options.strict);
+        assert!(Day3::parse(input, &options).is_err());
+        assert!(Day3::parse("81\n", &BatteryOptions::default()).is_ok());
+        assert!(Day3::parse("8x\n", &BatteryOptions::default()).is_err());
+    }
+
+    #[test]
+    fn parse_errors_point_to_banks() {
+        let error = Day3::parse("9876\n98x6\n", &BatteryOptions::default()).unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert_eq!(error.message(), "invalid battery: 'x'");
+
+        let options = BatteryOptions {
+            strict: true,
+            ..BatteryOptions::default()
+        };
+        let error = Day3::parse("987654321111111\n9876\n", &options).unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+        assert_eq!((error.line(), error.column()), (2, 1));
+        assert_eq!(error.message(), "bank has fewer than 12 batteries");
+    }
+
+    #[test]
+    fn select_batteries_works() {
+        let none = Constraints::default();
+        let no_leading_zero = Constraints {
+            no_leading_zero: true,
+            ..Constraints::default()
+        };
+
+        assert_eq!(
+            select_min_batteries(b"818181911112111", 4),
+            Selection {
+                indices: vec![1, 3, 5, 7],
+                value: 1111,
+            }
+        );
+        assert_eq!(select_min_batteries(b"3102", 2).value, 2);
+        assert_eq!(
+            select_batteries(b"3102", 2, Goal::Min, &no_leading_zero),
+            Some(Selection {
+                indices: vec![1, 2],
+                value: 10,
+            })
+        );
+        assert_eq!(
+            select_batteries(b"000", 1, Goal::Min, &no_leading_zero),
+            None
+        );
+        assert_eq!(select_batteries(b"12", 3, Goal::Max, &none), None);
+
+        let window = Constraints {
+            window: Some(Window { max: 1, len: 2 }),
+            ..Constraints::default()
+        };
+        assert_eq!(
+            select_batteries(b"99919", 3, Goal::Max, &window),
+            Some(Selection {
+                indices: vec![0, 2, 4],
+                value: 999,
+            })
+        );
+        assert_eq!(select_batteries(b"9999", 3, Goal::Max, &window), None);
+
+        let forbidden = Constraints {
+            forbidden: vec![6, 20],
+            ..Constraints::default()
+        };
+        assert_eq!(
+            select_batteries(b"818181911112111", 2, Goal::Max, &forbidden)
+                .unwrap()
+                .value,
+            88
+        );
+    }
+
+    #[test]
+    fn constraint_options_work() {
+        let mut options = BatteryOptions::default();
+        let mut args = ["2/5", "1,3"].into_iter();
+
+        assert!(options.parse_option("--min", &mut args).unwrap());
+        assert!(
+            options
+                .parse_option("--no-leading-zero", &mut args)
+                .unwrap()
+        );
+        assert!(options.parse_option("--window", &mut args).unwrap());
+        assert!(options.parse_option("--forbid", &mut args).unwrap());
+        assert_eq!(
+            options,
+            BatteryOptions {
+                goal: Goal::Min,
+                constraints: Constraints {
+                    no_leading_zero: true,
+                    window: Some(Window { max: 2, len: 5 }),
+                    forbidden: vec![1, 3],
+                },
+                ..BatteryOptions::default()
+            }
+        );
+
+        for window in ["0/5", "2/0", "2", "2/x"] {
+            assert!(
+                options
+                    .parse_option("--window", &mut [window].into_iter())
+                    .is_err()
+            );
+        }
+        assert!(
+            options
+                .parse_option("--forbid", &mut ["1,,3"].into_iter())
+                .is_err()
+        );
+
+        options.batteries_1 = 2;
+        options.batteries_2 = 4;
+        let input = Day3::parse("9019\n1111\n", &options).unwrap();
+        assert_eq!(Day3::part_1(&input).unwrap(), 91 + 11);
+        assert!(Day3::part_2(&input).is_err());
+    }
+
+    // Finds the best selection by checking all combinations of batteries.
+    fn brute_force(bank: &[u8], n: usize, goal: Goal, constraints: &Constraints) -> Option<u128> {
+        let values = (0u32..1 << bank.len()).filter_map(|mask| {
+            let indices = (0..bank.len())
+                .filter(|&i| mask & 1 << i != 0)
+                .collect::<Vec<_>>();
+
+            let valid = indices.len() == n
+                && indices.iter().all(|i| !constraints.forbidden.contains(i))
+                && !(constraints.no_leading_zero
+                    && indices.first().is_some_and(|&i| bank[i] == b'0'))
+                && constraints.window.is_none_or(|Window { max, len }| {
+                    (0..bank.len()).all(|start| {
+                        indices
+                            .iter()
+                            .filter(|&&i| i >= start && i < start + len)
+                            .count()
+                            <= max
+                    })
+                });
+
+            valid.then(|| {
+                indices
+                    .iter()
+                    .fold(0, |acc, &i| 10 * acc + (bank[i] - b'0') as u128)
+            })
+        });
+
+        match goal {
+            Goal::Max => values.max(),
+            Goal::Min => values.min(),
+        }
+    }
+
+    fn constraints() -> impl Strategy<Value = Constraints> {
+        (
+            any::<bool>(),
+            proptest::option::of((1..4usize, 1..6usize)),
+            vec(0..10usize, 0..4),
+        )
+            .prop_map(|(no_leading_zero, window, forbidden)| Constraints {
+                no_leading_zero,
+                window: window.map(|(max, len)| Window { max, len }),
+                forbidden,
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn select_batteries_matches_brute_force(
+            bank in vec(b'0'..=b'9', 0..10),
+            n in 0..6usize,
+            goal in prop_oneof![Just(Goal::Max), Just(Goal::Min)],
+            constraints in constraints(),
+        ) {
+            let selection = select_batteries(&bank, n, goal, &constraints);
+
+            prop_assert_eq!(
+                selection.as_ref().map(|selection| selection.value),
+                brute_force(&bank, n, goal, &constraints)
+            );
+
+            if let Some(Selection { indices, value }) = selection {
+                prop_assert_eq!(indices.len(), n);
+                prop_assert!(indices.windows(2).all(|w| w[0] < w[1]));
+                prop_assert_eq!(
+                    indices.iter().fold(0, |acc, &i| 10 * acc + (bank[i] - b'0') as u128),
+                    value
+                );
+            }
+        }
+
+        #[test]
+        fn swap_loop_matches_search(bank in vec(b'0'..=b'9', 1..40), n in 0..12usize) {
+            let n = n.min(bank.len());
+            // Forbidding a battery past the end of the bank doesn't change the
+            // selection, but makes `select_batteries` search for it.
+            let constraints = Constraints {
+                forbidden: vec![bank.len()],
+                ..Constraints::default()
+            };
+
+            prop_assert_eq!(
+                select_batteries(&bank, n, Goal::Max, &constraints),
+                Some(select_max_batteries(&bank, n))
+            );
+            prop_assert_eq!(
+                select_batteries(&bank, n, Goal::Min, &constraints),
+                Some(select_min_batteries(&bank, n))
+            );
+        }
+    }
+}
@@ -1,68 +1,5 @@
-use std::{io, mem};
+use day_3::Day3;
 
-use anyhow::{Result, ensure};
-
-fn find_max_joltage(bank: &[u8], n: usize) -> u64 {
-    // First, we select the last `n` batteries. Then we go through the rest of
-    // the batteries right-to-left and try to increase the total joltage by
-    // adjusting the selection.
-
-    let mut selected = bank[bank.len() - n..].to_vec();
-
-    for i in (0..bank.len() - n).rev() {
-        let mut b = bank[i];
-
-        for j in 0..n {
-            if b >= selected[j] {
-                mem::swap(&mut b, &mut selected[j]);
-            } else {
-                break;
-            }
-        }
-    }
-
-    selected
-        .into_iter()
-        .fold(0, |acc, b| 10 * acc + (b - b'0') as u64)
-}
-
-fn main() -> Result<()> {
-    let mut joltage_2 = 0;
-    let mut joltage_12 = 0;
-
-    for line in io::stdin().lines() {
-        let line = line?;
-        let bank = line.as_bytes();
-
-        ensure!(bank.len() >= 12, "insufficient bank length: {}", bank.len());
-        ensure!(
-            bank.iter().all(|&b| b >= b'0' && b <= b'9'),
-            "invalid bank: {line:?}"
-        );
-
-        joltage_2 += find_max_joltage(bank, 2);
-        joltage_12 += find_max_joltage(bank, 12);
-    }
-
-    println!("{}", joltage_2);
-    println!("{}", joltage_12);
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn find_max_joltage_works() {
-        assert_eq!(find_max_joltage(b"987654321111111", 2), 98);
-        assert_eq!(find_max_joltage(b"811111111111119", 2), 89);
-        assert_eq!(find_max_joltage(b"234234234234278", 2), 78);
-        assert_eq!(find_max_joltage(b"818181911112111", 2), 92);
-
-        assert_eq!(find_max_joltage(b"987654321111111", 12), 987654321111);
-        assert_eq!(find_max_joltage(b"811111111111119", 12), 811111111119);
-        assert_eq!(find_max_joltage(b"234234234234278", 12), 434234234278);
-        assert_eq!(find_max_joltage(b"818181911112111", 12), 888911112111);
-    }
+fn main() {
+    solver::main::<Day3>(3);
 }
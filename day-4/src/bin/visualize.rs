@@ -0,0 +1,98 @@
+// Visualizes the removal of accessible rolls (see `visualize` in the library).
+// Reads the grid from the standard input and writes text frames of all rounds,
+// followed by a heatmap, to the standard output. With `--ppm`, the frames and
+// the heatmap are also written as PPM images to the given directory.
+
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use anyhow::{Context, Result, anyhow, bail};
+use day_4::Rule;
+use day_4::visualize::{self, Visualization};
+use solver::{Options, Solver, option_value};
+
+#[derive(Debug)]
+struct Args {
+    rule: Rule,
+    ppm: Option<PathBuf>,
+    scale: usize,
+}
+
+fn parse_args(args: &[String]) -> Result<Args> {
+    let mut args = args.iter().map(String::as_str);
+
+    let mut rule = Rule::default();
+    let mut ppm = None;
+    let mut scale = 4;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--ppm" => ppm = Some(PathBuf::from(option_value(&mut args, arg)?)),
+            "--scale" => {
+                let value = option_value(&mut args, arg)?;
+
+                scale = match value.parse() {
+                    Ok(scale) if scale > 0 => scale,
+                    _ => bail!("invalid scale: {value:?}"),
+                };
+            }
+            _ => {
+                if !rule.parse_option(arg, &mut args)? {
+                    bail!("unknown option: {arg:?}");
+                }
+            }
+        }
+    }
+
+    Ok(Args { rule, ppm, scale })
+}
+
+fn write_file(path: PathBuf, contents: &[u8]) -> Result<()> {
+    fs::write(&path, contents).with_context(|| format!("can't write {}", path.display()))
+}
+
+fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let args = parse_args(&args).map_err(|e| {
+        anyhow!(
+            "{e}\n\nusage: visualize [--ppm <DIR>] [--scale <N>] {}",
+            Rule::USAGE
+        )
+    })?;
+
+    let input = io::read_to_string(io::stdin())?;
+    let grid = day_4::Day4::parse(&input, &args.rule)?;
+
+    let visualization = Visualization::new(&grid);
+
+    for (round, frame) in visualization.frames.iter().enumerate() {
+        println!("round {round}:");
+        println!("{}", visualize::render_frame(frame));
+    }
+
+    println!("heatmap:");
+    print!("{}", visualize::render_heatmap(&visualization.heatmap));
+
+    if let Some(dir) = args.ppm {
+        fs::create_dir_all(&dir).with_context(|| format!("can't create {}", dir.display()))?;
+
+        for (round, frame) in visualization.frames.iter().enumerate() {
+            write_file(
+                dir.join(format!("round-{round:03}.ppm")),
+                &visualize::frame_ppm(frame, args.scale),
+            )?;
+        }
+
+        write_file(
+            dir.join("heatmap.ppm"),
+            &visualize::heatmap_ppm(
+                &visualization.heatmap,
+                visualization.round_count(),
+                args.scale,
+            ),
+        )?;
+    }
+
+    Ok(())
+}
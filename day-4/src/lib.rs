@@ -0,0 +1,567 @@
+use anyhow::{Error, Result, anyhow, bail, ensure};
+use solver::{Options, Solver, option_value};
+
+pub mod visualize;
+
+/// Maximum radius accepted by `--neighbourhood radius:<R>` (a neighbourhood with
+/// radius `r` has `(2r + 1)^2 - 1` cells, each visited for every roll).
+pub const MAX_RADIUS: usize = 100;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Cell {
+    Empty,
+    Roll,
+}
+
+/// Cells whose rolls are counted as adjacent to a roll, given as offsets from
+/// its position.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Neighbourhood {
+    /// The 8 surrounding cells.
+    Moore,
+    /// The 4 orthogonally adjacent cells.
+    VonNeumann,
+    /// All cells at most `r` rows and columns away (radius 1 is the same as
+    /// Moore).
+    Radius(usize),
+    Custom(Vec<(isize, isize)>),
+}
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> Vec<(isize, isize)> {
+        match self {
+            Neighbourhood::Moore => grid::OFFSETS_8.to_vec(),
+            Neighbourhood::VonNeumann => grid::OFFSETS_4.to_vec(),
+            Neighbourhood::Radius(r) => {
+                let r = *r as isize;
+
+                (-r..=r)
+                    .flat_map(|dy| (-r..=r).map(move |dx| (dx, dy)))
+                    .filter(|&offset| offset != (0, 0))
+                    .collect()
+            }
+            Neighbourhood::Custom(offsets) => offsets.clone(),
+        }
+    }
+}
+
+/// Rule deciding which rolls are accessible by a forklift: those with fewer
+/// than `threshold` rolls in their neighbourhood. With `wrap`, the neighbourhood
+/// wraps around the edges of the grid.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Rule {
+    pub neighbourhood: Neighbourhood,
+    pub threshold: usize,
+    pub wrap: bool,
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule {
+            neighbourhood: Neighbourhood::Moore,
+            threshold: 4,
+            wrap: false,
+        }
+    }
+}
+
+impl Options for Rule {
+    const USAGE: &'static str = "[--neighbourhood <moore|von-neumann|radius:<R>|<DX>,<DY>;...>] \
+                                 [--threshold <N>] [--wrap]";
+
+    fn parse_option(&mut self, option: &str, args: &mut dyn Iterator<Item = &str>) -> Result<bool> {
+        match option {
+            "--neighbourhood" => {
+                self.neighbourhood = parse_neighbourhood(option_value(args, option)?)?
+            }
+            "--threshold" => {
+                let value = option_value(args, option)?;
+
+                self.threshold = value
+                    .parse()
+                    .map_err(|_| anyhow!("invalid threshold: {value:?}"))?;
+            }
+            "--wrap" => self.wrap = true,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+}
+
+fn parse_neighbourhood(s: &str) -> Result<Neighbourhood> {
+    if s == "moore" {
+        return Ok(Neighbourhood::Moore);
+    }
+    if s == "von-neumann" {
+        return Ok(Neighbourhood::VonNeumann);
+    }
+    if let Some(r) = s.strip_prefix("radius:") {
+        let r = r.parse().map_err(|_| anyhow!("invalid radius: {r:?}"))?;
+
+        ensure!(
+            r <= MAX_RADIUS,
+            "radius can't be greater than {MAX_RADIUS}: {r}"
+        );
+
+        return Ok(Neighbourhood::Radius(r));
+    }
+
+    let offsets = s
+        .split(';')
+        .map(|offset| {
+            let Some((dx, dy)) = offset.split_once(',') else {
+                bail!("invalid offset: {offset:?}");
+            };
+            let (Ok(dx), Ok(dy)) = (dx.trim().parse(), dy.trim().parse()) else {
+                bail!("invalid offset: {offset:?}");
+            };
+
+            ensure!((dx, dy) != (0, 0), "offset can't be zero: {offset:?}");
+
+            Ok((dx, dy))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Neighbourhood::Custom(offsets))
+}
+
+#[derive(Clone, Debug)]
+pub struct Grid {
+    cells: grid::Grid<Cell>,
+    rule: Rule,
+    // Offsets of the cells whose neighbourhood contains the cell at (0, 0),
+    // i.e. the negated offsets of the neighbourhood. (They are the same as the
+    // neighbourhood offsets unless the neighbourhood is asymmetric.)
+    inverse_offsets: Vec<(isize, isize)>,
+    adjacent_roll_counts: grid::Grid<usize>,
+}
+
+impl Grid {
+    pub fn new(width: usize, height: usize) -> Grid {
+        Grid::with_rule(width, height, Rule::default())
+    }
+
+    pub fn with_rule(width: usize, height: usize, rule: Rule) -> Grid {
+        let inverse_offsets = rule
+            .neighbourhood
+            .offsets()
+            .into_iter()
+            .map(|(dx, dy)| (-dx, -dy))
+            .collect();
+
+        Grid {
+            cells: grid::Grid::new(width, height, Cell::Empty),
+            rule,
+            inverse_offsets,
+            adjacent_roll_counts: grid::Grid::new(width, height, 0),
+        }
+    }
+
+    pub fn add_roll(&mut self, x: usize, y: usize) {
+        assert!(self.cell(x, y) == Cell::Empty);
+
+        self.cells[(x, y)] = Cell::Roll;
+        self.update_adjacent_roll_counts(x, y, 1);
+    }
+
+    pub fn remove_roll(&mut self, x: usize, y: usize) {
+        assert!(self.cell(x, y) == Cell::Roll);
+
+        self.cells[(x, y)] = Cell::Empty;
+        self.update_adjacent_roll_counts(x, y, -1);
+    }
+
+    pub fn accessible_roll_count(&self) -> usize {
+        self.accessible_roll_coords().len()
+    }
+
+    pub fn remove_accessible_rolls(&mut self) -> usize {
+        let coords = self.accessible_roll_coords();
+        let coords_len = coords.len();
+
+        for (x, y) in coords {
+            self.remove_roll(x, y)
+        }
+
+        coords_len
+    }
+
+    /// Repeatedly removes accessible rolls until there are none left and
+    /// returns coordinates of the rolls removed in each round.
+    pub fn remove_all_accessible_rolls(&mut self) -> Vec<Vec<(usize, usize)>> {
+        // All accessible rolls are removed in each round, so a roll can become
+        // accessible only when an adjacent roll is removed. That means it's
+        // enough to re-examine neighbours of the removed rolls instead of
+        // rescanning the whole grid.
+        let mut queued = grid::Grid::new(self.width(), self.height(), false);
+        let mut rounds = vec![];
+
+        let mut round = self.accessible_roll_coords();
+
+        while !round.is_empty() {
+            for &(x, y) in &round {
+                self.remove_roll(x, y);
+            }
+
+            let mut next_round = vec![];
+
+            for &(x, y) in &round {
+                for (x, y) in self.inverse_neighbours(x, y) {
+                    if self.is_accessible(x, y) && !queued[(x, y)] {
+                        queued[(x, y)] = true;
+                        next_round.push((x, y));
+                    }
+                }
+            }
+
+            rounds.push(round);
+            round = next_round;
+        }
+
+        rounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn cell(&self, x: usize, y: usize) -> Cell {
+        self.cells[(x, y)]
+    }
+
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    fn adjacent_roll_count(&self, x: usize, y: usize) -> usize {
+        self.adjacent_roll_counts[(x, y)]
+    }
+
+    fn update_adjacent_roll_counts(&mut self, x: usize, y: usize, delta: isize) {
+        for (x, y) in self.inverse_neighbours(x, y) {
+            let count = &mut self.adjacent_roll_counts[(x, y)];
+
+            *count = count.checked_add_signed(delta).unwrap();
+        }
+    }
+
+    // Returns coordinates of the cells whose neighbourhood contains the given
+    // cell. (A cell may be returned multiple times if the neighbourhood wraps
+    // around a small grid.)
+    fn inverse_neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        if self.rule.wrap {
+            self.cells
+                .neighbours_wrapping(x, y, &self.inverse_offsets)
+                .collect()
+        } else {
+            self.cells.neighbours(x, y, &self.inverse_offsets).collect()
+        }
+    }
+
+    fn is_accessible(&self, x: usize, y: usize) -> bool {
+        self.cell(x, y) == Cell::Roll && self.adjacent_roll_count(x, y) < self.rule.threshold
+    }
+
+    fn accessible_roll_coords(&self) -> Vec<(usize, usize)> {
+        self.cells
+            .coords()
+            .filter(|&(x, y)| self.is_accessible(x, y))
+            .collect()
+    }
+}
+
+fn parse_grid(input: &str, rule: &Rule) -> Result<Grid> {
+    let cells = grid::Grid::parse(input, |ch| match ch {
+        '.' => Some(Cell::Empty),
+        '@' => Some(Cell::Roll),
+        _ => None,
+    })?;
+
+    let mut grid = Grid::with_rule(cells.width(), cells.height(), rule.clone());
+
+    for (x, y) in cells.coords() {
+        if cells[(x, y)] == Cell::Roll {
+            grid.add_roll(x, y);
+        }
+    }
+
+    Ok(grid)
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = Grid;
+    type Options = Rule;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = Error;
+
+    fn parse(input: &str, rule: &Rule) -> Result<Grid> {
+        parse_grid(input, rule)
+    }
+
+    fn part_1(grid: &Grid) -> Result<usize> {
+        Ok(grid.accessible_roll_count())
+    }
+
+    fn part_2(grid: &Grid) -> Result<usize> {
+        let mut grid = grid.clone();
+
+        let count = grid
+            .remove_all_accessible_rolls()
+            .iter()
+            .map(Vec::len)
+            .sum();
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    // Counts rolls adjacent to a cell from scratch, without relying on the
+    // incrementally updated counts.
+    fn recount_adjacent_rolls(grid: &Grid, x: usize, y: usize) -> usize {
+        let (width, height) = (grid.width() as isize, grid.height() as isize);
+        let mut count = 0;
+
+        for (dx, dy) in grid.rule().neighbourhood.offsets() {
+            let (mut nx, mut ny) = (x as isize + dx, y as isize + dy);
+
+            if grid.rule().wrap {
+                nx = nx.rem_euclid(width);
+                ny = ny.rem_euclid(height);
+            }
+
+            if (0..width).contains(&nx)
+                && (0..height).contains(&ny)
+                && grid.cell(nx as usize, ny as usize) == Cell::Roll
+            {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    const EXAMPLE: [&str; 10] = [
+        "..@@.@@@@.",
+        "@@@.@.@.@@",
+        "@@@@@.@.@@",
+        "@.@@@@..@.",
+        "@@.@@@@.@@",
+        ".@@@@@@@.@",
+        ".@.@.@.@@@",
+        "@.@@@.@@@@",
+        ".@@@@@@@@.",
+        "@.@.@@@.@.",
+    ];
+
+    #[test]
+    fn grid_works() {
+        let mut grid = Grid::new(10, 10);
+
+        for (y, line) in EXAMPLE.iter().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                if ch == '@' {
+                    grid.add_roll(x, y);
+                }
+            }
+        }
+
+        assert_eq!(grid.accessible_roll_count(), 13);
+
+        assert_eq!(grid.remove_accessible_rolls(), 13);
+        assert_eq!(grid.remove_accessible_rolls(), 12);
+        assert_eq!(grid.remove_accessible_rolls(), 7);
+        assert_eq!(grid.remove_accessible_rolls(), 5);
+        assert_eq!(grid.remove_accessible_rolls(), 2);
+        assert_eq!(grid.remove_accessible_rolls(), 1);
+        assert_eq!(grid.remove_accessible_rolls(), 1);
+        assert_eq!(grid.remove_accessible_rolls(), 1);
+        assert_eq!(grid.remove_accessible_rolls(), 1);
+        assert_eq!(grid.remove_accessible_rolls(), 0);
+    }
+
+    #[test]
+    fn remove_all_accessible_rolls_works() {
+        let mut grid = parse_grid(&EXAMPLE.join("\n"), &Rule::default()).unwrap();
+
+        let round_counts = grid
+            .remove_all_accessible_rolls()
+            .iter()
+            .map(Vec::len)
+            .collect::<Vec<_>>();
+
+        assert_eq!(round_counts, vec![13, 12, 7, 5, 2, 1, 1, 1, 1]);
+        assert_eq!(grid.accessible_roll_count(), 0);
+    }
+
+    #[test]
+    fn non_square_grid_works() {
+        for input in ["@@@@@\n@@@@@\n", "@@\n@@\n@@\n@@\n@@\n"] {
+            let mut grid = parse_grid(input, &Rule::default()).unwrap();
+
+            assert_eq!(grid.accessible_roll_count(), 4);
+
+            assert_eq!(grid.remove_accessible_rolls(), 4);
+            assert_eq!(grid.remove_accessible_rolls(), 4);
+            assert_eq!(grid.remove_accessible_rolls(), 2);
+            assert_eq!(grid.remove_accessible_rolls(), 0);
+        }
+    }
+
+    #[test]
+    fn rule_works() {
+        let input = "@@@\n@@@\n@@@\n";
+
+        let rule = Rule {
+            neighbourhood: Neighbourhood::VonNeumann,
+            ..Rule::default()
+        };
+        assert_eq!(parse_grid(input, &rule).unwrap().accessible_roll_count(), 8);
+
+        let rule = Rule {
+            threshold: 6,
+            ..Rule::default()
+        };
+        assert_eq!(parse_grid(input, &rule).unwrap().accessible_roll_count(), 8);
+
+        let rule = Rule {
+            wrap: true,
+            ..Rule::default()
+        };
+        assert_eq!(parse_grid(input, &rule).unwrap().accessible_roll_count(), 0);
+
+        let rule = Rule {
+            neighbourhood: Neighbourhood::Custom(vec![(1, 0), (2, 0)]),
+            threshold: 1,
+            wrap: false,
+        };
+        assert_eq!(parse_grid(input, &rule).unwrap().accessible_roll_count(), 3);
+    }
+
+    #[test]
+    fn rule_options_work() {
+        let mut rule = Rule::default();
+        let mut args = ["radius:2", "7", "-1,0; 0,2"].into_iter();
+
+        assert!(rule.parse_option("--neighbourhood", &mut args).unwrap());
+        assert!(rule.parse_option("--threshold", &mut args).unwrap());
+        assert!(rule.parse_option("--wrap", &mut args).unwrap());
+        assert_eq!(
+            rule,
+            Rule {
+                neighbourhood: Neighbourhood::Radius(2),
+                threshold: 7,
+                wrap: true,
+            }
+        );
+        assert_eq!(Neighbourhood::Radius(2).offsets().len(), 24);
+
+        assert!(rule.parse_option("--neighbourhood", &mut args).unwrap());
+        assert_eq!(
+            rule.neighbourhood,
+            Neighbourhood::Custom(vec![(-1, 0), (0, 2)])
+        );
+
+        assert!(parse_neighbourhood("0,0").is_err());
+        assert!(parse_neighbourhood("1;0").is_err());
+        assert!(parse_neighbourhood(&format!("radius:{MAX_RADIUS}")).is_ok());
+        assert!(parse_neighbourhood(&format!("radius:{}", MAX_RADIUS + 1)).is_err());
+        assert!(parse_neighbourhood("radius:100000").is_err());
+        assert!(!rule.parse_option("--unknown", &mut args).unwrap());
+    }
+
+    fn rule() -> impl Strategy<Value = Rule> {
+        let neighbourhood = prop_oneof![
+            Just(Neighbourhood::Moore),
+            Just(Neighbourhood::VonNeumann),
+            (0..3usize).prop_map(Neighbourhood::Radius),
+            vec((-2..=2isize, -2..=2isize), 0..6).prop_map(Neighbourhood::Custom),
+        ];
+
+        (neighbourhood, 0..10usize, any::<bool>()).prop_map(|(neighbourhood, threshold, wrap)| {
+            Rule {
+                neighbourhood,
+                threshold,
+                wrap,
+            }
+        })
+    }
+
+    fn grid_and_toggles() -> impl Strategy<Value = (usize, usize, Vec<(usize, usize)>)> {
+        (1..12usize, 1..12usize).prop_flat_map(|(width, height)| {
+            (
+                Just(width),
+                Just(height),
+                vec((0..width, 0..height), 0..200),
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn adjacent_roll_counts_match_recount(
+            (width, height, toggles) in grid_and_toggles(),
+            rule in rule(),
+        ) {
+            let mut grid = Grid::with_rule(width, height, rule);
+
+            for (x, y) in toggles {
+                match grid.cell(x, y) {
+                    Cell::Empty => grid.add_roll(x, y),
+                    Cell::Roll => grid.remove_roll(x, y),
+                }
+            }
+
+            for y in 0..height {
+                for x in 0..width {
+                    prop_assert_eq!(
+                        grid.adjacent_roll_count(x, y),
+                        recount_adjacent_rolls(&grid, x, y)
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn remove_all_accessible_rolls_matches_rescans(
+            (width, height, rolls) in grid_and_toggles(),
+            rule in rule(),
+        ) {
+            let mut grid = Grid::with_rule(width, height, rule);
+
+            for (x, y) in rolls {
+                if grid.cell(x, y) == Cell::Empty {
+                    grid.add_roll(x, y);
+                }
+            }
+
+            let mut rescanned_grid = grid.clone();
+            let mut rescanned_rounds = vec![];
+
+            while let count = rescanned_grid.remove_accessible_rolls()
+                && count > 0
+            {
+                rescanned_rounds.push(count);
+            }
+
+            let rounds = grid.remove_all_accessible_rolls();
+
+            prop_assert_eq!(rounds.iter().map(Vec::len).collect::<Vec<_>>(), rescanned_rounds);
+            prop_assert_eq!(grid.cells, rescanned_grid.cells);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// Visualization of the removal of accessible rolls. The grid is rendered after
+// every round, with the rolls removed in that round marked, and at the end, a
+// heatmap shows the round in which each roll was removed.
+//
+// Frames and the heatmap can be rendered either as text or as PPM images, which
+// don't need any library to write and which most image viewers (and tools like
+// ImageMagick or FFmpeg) can read.
+
+use crate::{Cell, Grid};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FrameCell {
+    Empty,
+    Roll,
+    /// The roll was removed in the round shown by the frame.
+    Removed,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum HeatCell {
+    Empty,
+    /// The roll was never removed.
+    Roll,
+    /// The roll was removed in the given round (starting from 1).
+    Removed(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct Visualization {
+    /// Frames showing the grid before the first round and after each round.
+    pub frames: Vec<grid::Grid<FrameCell>>,
+    pub heatmap: grid::Grid<HeatCell>,
+}
+
+impl Visualization {
+    /// Removes all accessible rolls from a copy of the grid and records what
+    /// happens in each round.
+    pub fn new(grid: &Grid) -> Visualization {
+        let mut frame = grid.cells.map(|&cell| match cell {
+            Cell::Empty => FrameCell::Empty,
+            Cell::Roll => FrameCell::Roll,
+        });
+        let mut heatmap = grid.cells.map(|&cell| match cell {
+            Cell::Empty => HeatCell::Empty,
+            Cell::Roll => HeatCell::Roll,
+        });
+
+        let mut frames = vec![frame.clone()];
+
+        for (i, round) in grid
+            .clone()
+            .remove_all_accessible_rolls()
+            .iter()
+            .enumerate()
+        {
+            for cell in frame.iter_mut() {
+                if *cell == FrameCell::Removed {
+                    *cell = FrameCell::Empty;
+                }
+            }
+
+            for &(x, y) in round {
+                frame[(x, y)] = FrameCell::Removed;
+                heatmap[(x, y)] = HeatCell::Removed(i + 1);
+            }
+
+            frames.push(frame.clone());
+        }
+
+        Visualization { frames, heatmap }
+    }
+
+    pub fn round_count(&self) -> usize {
+        self.frames.len() - 1
+    }
+}
+
+/// Renders a frame as text, with removed rolls marked as `x`.
+pub fn render_frame(frame: &grid::Grid<FrameCell>) -> String {
+    frame.render(|cell| match cell {
+        FrameCell::Empty => '.',
+        FrameCell::Roll => '@',
+        FrameCell::Removed => 'x',
+    })
+}
+
+/// Renders a heatmap as text. Rounds 1-9 are shown as digits, rounds 10-35 as
+/// letters `a`-`z`, and later rounds as `+`.
+pub fn render_heatmap(heatmap: &grid::Grid<HeatCell>) -> String {
+    heatmap.render(|cell| match cell {
+        HeatCell::Empty => '.',
+        HeatCell::Roll => '@',
+        HeatCell::Removed(round) => char::from_digit(*round as u32, 36).unwrap_or('+'),
+    })
+}
+
+/// Encodes a frame as a PPM image, with each cell drawn as a square of
+/// `scale` × `scale` pixels.
+pub fn frame_ppm(frame: &grid::Grid<FrameCell>, scale: usize) -> Vec<u8> {
+    ppm(frame, scale, |cell| match cell {
+        FrameCell::Empty => [32, 32, 32],
+        FrameCell::Roll => [200, 200, 200],
+        FrameCell::Removed => [230, 40, 40],
+    })
+}
+
+/// Encodes a heatmap as a PPM image (see `frame_ppm`). Rolls removed in early
+/// rounds are yellow and rolls removed in late rounds are dark red.
+pub fn heatmap_ppm(heatmap: &grid::Grid<HeatCell>, round_count: usize, scale: usize) -> Vec<u8> {
+    const EARLY: [f64; 3] = [255.0, 230.0, 0.0];
+    const LATE: [f64; 3] = [140.0, 0.0, 0.0];
+
+    ppm(heatmap, scale, |cell| match cell {
+        HeatCell::Empty => [32, 32, 32],
+        HeatCell::Roll => [200, 200, 200],
+        HeatCell::Removed(round) => {
+            let t = if round_count > 1 {
+                (round - 1) as f64 / (round_count - 1) as f64
+            } else {
+                0.0
+            };
+
+            [0, 1, 2].map(|i| (EARLY[i] + (LATE[i] - EARLY[i]) * t).round() as u8)
+        }
+    })
+}
+
+fn ppm<T>(grid: &grid::Grid<T>, scale: usize, color: impl Fn(&T) -> [u8; 3]) -> Vec<u8> {
+    let width = grid.width() * scale;
+    let height = grid.height() * scale;
+
+    let mut ppm = format!("P6\n{width} {height}\n255\n").into_bytes();
+    ppm.reserve(width * height * 3);
+
+    for row in grid.rows() {
+        for _ in 0..scale {
+            for cell in row {
+                let color = color(cell);
+
+                for _ in 0..scale {
+                    ppm.extend_from_slice(&color);
+                }
+            }
+        }
+    }
+
+    ppm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rule, parse_grid};
+
+    #[test]
+    fn visualization_works() {
+        let grid = parse_grid("@@@@@\n@@@@@\n", &Rule::default()).unwrap();
+        let visualization = Visualization::new(&grid);
+
+        let frames = visualization
+            .frames
+            .iter()
+            .map(render_frame)
+            .collect::<Vec<_>>();
+
+        assert_eq!(visualization.round_count(), 3);
+        assert_eq!(
+            frames,
+            vec![
+                "@@@@@\n@@@@@\n",
+                "x@@@x\nx@@@x\n",
+                ".x@x.\n.x@x.\n",
+                "..x..\n..x..\n",
+            ]
+        );
+        assert_eq!(render_heatmap(&visualization.heatmap), "12321\n12321\n");
+
+        let ppm = frame_ppm(&visualization.frames[1], 2);
+
+        assert!(ppm.starts_with(b"P6\n10 4\n255\n"));
+        assert_eq!(ppm.len(), "P6\n10 4\n255\n".len() + 10 * 4 * 3);
+        assert_eq!(&ppm[ppm.len() - 3..], &[230, 40, 40]);
+    }
+}
@@ -0,0 +1,137 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{Error, Result, ensure};
+use input::{Line, ParseError};
+use interval_set::IntervalSet;
+use solver::Solver;
+
+/// Set of fresh ingredient IDs.
+#[derive(Clone, Default, Debug)]
+pub struct FreshIngredients {
+    ids: IntervalSet<u64>,
+}
+
+impl FreshIngredients {
+    pub fn new() -> FreshIngredients {
+        FreshIngredients::default()
+    }
+
+    pub fn add_range(&mut self, range: RangeInclusive<u64>) {
+        self.ids.add_range(range);
+    }
+
+    pub fn is_fresh(&self, id: u64) -> bool {
+        self.ids.contains(id)
+    }
+
+    pub fn fresh_count(&self) -> u128 {
+        self.ids.len()
+    }
+}
+
+fn parse_fresh_id_range(line: &Line) -> Result<RangeInclusive<u64>, ParseError> {
+    let (start, end) = line
+        .text
+        .split_once('-')
+        .ok_or_else(|| line.error("invalid fresh ingredient ID range"))?;
+
+    let start = line.parse_part(start, "fresh ingredient ID")?;
+    let end = line.parse_part(end, "fresh ingredient ID")?;
+
+    if start > end {
+        return Err(line.error("fresh ingredient ID range is reversed"));
+    }
+
+    Ok(start..=end)
+}
+
+fn parse_available_id(line: &Line) -> Result<u64, ParseError> {
+    line.parse_part(line.text, "available ingredient ID")
+}
+
+#[derive(Clone, Debug)]
+pub struct Database {
+    pub fresh_ingredients: FreshIngredients,
+    pub available_ids: Vec<u64>,
+}
+
+fn parse_database(input: &str) -> Result<Database> {
+    let sections = input::sections(input);
+
+    ensure!(
+        sections.len() <= 2,
+        "database has more than 2 sections: {}",
+        sections.len()
+    );
+
+    let mut fresh_ingredients = FreshIngredients::new();
+
+    for line in sections.first().into_iter().flatten() {
+        fresh_ingredients.add_range(parse_fresh_id_range(line)?);
+    }
+
+    let available_ids = sections
+        .get(1)
+        .into_iter()
+        .flatten()
+        .map(parse_available_id)
+        .collect::<Result<_, _>>()?;
+
+    let database = Database {
+        fresh_ingredients,
+        available_ids,
+    };
+
+    Ok(database)
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = Database;
+    type Options = ();
+    type Answer1 = usize;
+    type Answer2 = u128;
+    type Error = Error;
+
+    fn parse(input: &str, _: &()) -> Result<Database> {
+        parse_database(input)
+    }
+
+    fn part_1(database: &Database) -> Result<usize> {
+        let count = database
+            .available_ids
+            .iter()
+            .filter(|&&id| database.fresh_ingredients.is_fresh(id))
+            .count();
+
+        Ok(count)
+    }
+
+    fn part_2(database: &Database) -> Result<u128> {
+        Ok(database.fresh_ingredients.fresh_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_ingredients_works() {
+        let mut fresh_ingredients = FreshIngredients::new();
+        fresh_ingredients.add_range(3..=5);
+        fresh_ingredients.add_range(10..=14);
+        fresh_ingredients.add_range(16..=20);
+        fresh_ingredients.add_range(12..=18);
+
+        assert!(!fresh_ingredients.is_fresh(1));
+        assert!(fresh_ingredients.is_fresh(5));
+        assert!(!fresh_ingredients.is_fresh(8));
+        assert!(fresh_ingredients.is_fresh(11));
+        assert!(fresh_ingredients.is_fresh(17));
+        assert!(!fresh_ingredients.is_fresh(32));
+
+        assert_eq!(fresh_ingredients.fresh_count(), 14);
+    }
+}
@@ -0,0 +1,201 @@
+use std::mem;
+
+use anyhow::{Error, Result, bail};
+use input::{Line, ParseError};
+use solver::Solver;
+
+pub struct ManyZip<I> {
+    iters: Vec<I>,
+}
+
+impl<I> ManyZip<I>
+where
+    I: Iterator,
+{
+    pub fn new(iters: Vec<I>) -> Self {
+        Self { iters }
+    }
+}
+
+impl<I> Iterator for ManyZip<I>
+where
+    I: Iterator,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iters.is_empty() {
+            return None;
+        }
+
+        self.iters.iter_mut().map(|it| it.next()).collect()
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Op {
+    Add,
+    Mul,
+}
+
+// The numbers of each problem can be read in two ways: by rows (used in part
+// 1) and by columns (used in part 2). We parse both at once because it is
+// natural to do so when going through the worksheet column by column.
+
+#[derive(Clone, Debug)]
+pub struct Problem {
+    pub row_numbers: Vec<u64>,
+    pub col_numbers: Vec<u64>,
+    pub op: Op,
+}
+
+pub fn compute_problem(numbers: &[u64], op: Op) -> u64 {
+    match op {
+        Op::Add => numbers.iter().sum(),
+        Op::Mul => numbers.iter().product(),
+    }
+}
+
+// Checks that the line contains only valid characters, so that the worksheet
+// can be then read byte by byte.
+fn check_chars(line: &Line, is_valid: impl Fn(char) -> bool, what: &str) -> Result<(), ParseError> {
+    match line.text.char_indices().find(|&(_, ch)| !is_valid(ch)) {
+        Some((i, ch)) => {
+            let part = &line.text[i..i + ch.len_utf8()];
+
+            Err(line.error_at(part, format!("invalid {what}: {ch:?}")))
+        }
+        None => Ok(()),
+    }
+}
+
+fn parse_problems(input: &str) -> Result<Vec<Problem>> {
+    let lines = input::lines(input).collect::<Vec<_>>();
+
+    let Some((op_line, number_lines)) = lines.split_last() else {
+        bail!("worksheet is empty");
+    };
+
+    for line in number_lines {
+        check_chars(line, |ch| ch.is_ascii_digit() || ch == ' ', "digit")?;
+    }
+
+    check_chars(op_line, |ch| matches!(ch, '+' | '*' | ' '), "operation")?;
+
+    let bytes = lines
+        .iter()
+        .map(|line| line.text.bytes())
+        .collect::<Vec<_>>();
+    let cols = ManyZip::new(bytes);
+
+    let mut numbers_1 = vec![0; lines.len() - 1];
+    let mut numbers_2 = vec![];
+    let mut op = Op::Add;
+
+    let mut problems = vec![];
+
+    for col in cols {
+        if col.iter().all(|&b| b == b' ') {
+            problems.push(Problem {
+                row_numbers: mem::replace(&mut numbers_1, vec![0; lines.len() - 1]),
+                col_numbers: mem::take(&mut numbers_2),
+                op,
+            });
+
+            continue;
+        }
+
+        let mut number_2 = 0;
+
+        for i in 0..col.len() - 1 {
+            let b = col[i];
+            match b {
+                b'0'..=b'9' => {
+                    let digit = (b - b'0') as u64;
+
+                    numbers_1[i] = 10 * numbers_1[i] + digit;
+                    number_2 = 10 * number_2 + digit;
+                }
+                b' ' => (),
+                _ => unreachable!(),
+            }
+        }
+
+        numbers_2.push(number_2);
+
+        let b = col[col.len() - 1];
+        match b {
+            b'+' => op = Op::Add,
+            b'*' => op = Op::Mul,
+            b' ' => (),
+            _ => unreachable!(),
+        }
+    }
+
+    problems.push(Problem {
+        row_numbers: numbers_1,
+        col_numbers: numbers_2,
+        op,
+    });
+
+    Ok(problems)
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input = Vec<Problem>;
+    type Options = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+    type Error = Error;
+
+    fn parse(input: &str, _: &()) -> Result<Vec<Problem>> {
+        parse_problems(input)
+    }
+
+    fn part_1(problems: &Vec<Problem>) -> Result<u64> {
+        let total = problems
+            .iter()
+            .map(|problem| compute_problem(&problem.row_numbers, problem.op))
+            .sum();
+
+        Ok(total)
+    }
+
+    fn part_2(problems: &Vec<Problem>) -> Result<u64> {
+        let total = problems
+            .iter()
+            .map(|problem| compute_problem(&problem.col_numbers, problem.op))
+            .sum();
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_problems_works() {
+        let input = "123 328\n 45 64 \n*   +  \n";
+        let problems = parse_problems(input).unwrap();
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].row_numbers, vec![123, 45]);
+        assert_eq!(problems[0].col_numbers, vec![1, 24, 35]);
+        assert_eq!(problems[1].row_numbers, vec![328, 64]);
+
+        let error = parse_problems("123 328\n 4x 64 \n*   +  \n").unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert_eq!(error.message(), "invalid digit: 'x'");
+
+        let error = parse_problems("123 328\n 45 64 \n*   -  \n").unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+        assert_eq!((error.line(), error.column()), (3, 5));
+
+        assert!(parse_problems("").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::{Error, Result};
+use grid::Grid;
+use solver::Solver;
+
+/// Traces the beams through the diagram and returns the number of times a beam
+/// was split and the number of beams that reached the bottom.
+pub fn trace_beams(diagram: &Grid<u8>) -> (u64, u64) {
+    let mut prev_beams = vec![0; diagram.width()];
+    let mut next_beams = vec![0; diagram.width()];
+
+    let mut count_1 = 0;
+
+    for row in diagram.rows() {
+        for j in 0..row.len() {
+            match row[j] {
+                b'S' => next_beams[j] += 1,
+                b'.' => next_beams[j] += prev_beams[j],
+                b'^' => {
+                    if j > 0 {
+                        next_beams[j - 1] += prev_beams[j];
+                    }
+                    if j < next_beams.len() - 1 {
+                        next_beams[j + 1] += prev_beams[j];
+                    }
+
+                    if prev_beams[j] > 0 {
+                        count_1 += 1;
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        prev_beams.copy_from_slice(&next_beams);
+        next_beams.fill(0);
+    }
+
+    let count_2 = prev_beams.into_iter().sum::<u64>();
+
+    (count_1, count_2)
+}
+
+fn parse_diagram(input: &str) -> Result<Grid<u8>> {
+    let diagram = Grid::parse(input, |ch| match ch {
+        'S' | '.' | '^' => Some(ch as u8),
+        _ => None,
+    })?;
+
+    Ok(diagram)
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = Grid<u8>;
+    type Options = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+    type Error = Error;
+
+    fn parse(input: &str, _: &()) -> Result<Grid<u8>> {
+        parse_diagram(input)
+    }
+
+    fn part_1(diagram: &Grid<u8>) -> Result<u64> {
+        Ok(trace_beams(diagram).0)
+    }
+
+    fn part_2(diagram: &Grid<u8>) -> Result<u64> {
+        Ok(trace_beams(diagram).1)
+    }
+}
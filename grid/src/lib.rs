@@ -0,0 +1,344 @@
+// Generic 2D grid. Cells are addressed by `(x, y)` coordinates, where `x` is the
+// column and `y` is the row, both starting from 0 at the top left corner.
+
+use std::ops::{Index, IndexMut};
+
+use input::{Line, ParseError};
+
+/// Offsets of the 4 orthogonal neighbours of a cell.
+pub const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the 8 neighbours of a cell (orthogonal and diagonal).
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // Cells stored row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with all cells set to `cell`.
+    pub fn new(width: usize, height: usize, cell: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// Creates a grid from a vector of rows, which must all have the same
+    /// length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+
+        assert!(rows.iter().all(|row| row.len() == width));
+
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Parses a grid from text, converting each character into a cell using
+    /// the `cell` function (which returns `None` for invalid characters). The
+    /// grid must be non-empty and rectangular.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let lines = input::lines(input).collect::<Vec<_>>();
+
+        Grid::parse_lines(&lines, cell)
+    }
+
+    /// Like `parse`, but parses the grid from lines which are part of a larger
+    /// input (so that errors point to the right place).
+    pub fn parse_lines(
+        lines: &[Line],
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        Ok(Grid::from_rows(input::grid(lines, cell)?))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height);
+
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width);
+
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Returns coordinates of all cells, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Returns coordinates of the neighbours of a cell at given offsets, skipping
+    /// those outside of the grid.
+    pub fn neighbours<'a>(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + use<'a, T> {
+        let (width, height) = (self.width, self.height);
+
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+
+            (x < width && y < height).then_some((x, y))
+        })
+    }
+
+    /// Like `neighbours`, but treats the grid as a torus, i.e. neighbours outside
+    /// of the grid wrap around to the opposite edge.
+    pub fn neighbours_wrapping<'a>(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + use<'a, T> {
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        offsets.iter().map(move |&(dx, dy)| {
+            let x = (x as isize + dx).rem_euclid(width);
+            let y = (y as isize + dy).rem_euclid(height);
+
+            (x as usize, y as usize)
+        })
+    }
+
+    /// Returns coordinates of the orthogonal neighbours of a cell.
+    pub fn neighbours_4(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        self.neighbours(x, y, &OFFSETS_4)
+    }
+
+    /// Returns coordinates of the orthogonal and diagonal neighbours of a cell.
+    pub fn neighbours_8(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        self.neighbours(x, y, &OFFSETS_8)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Returns the grid flipped over its main diagonal.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Returns the grid rotated by 90 degrees clockwise.
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    /// Returns the grid rotated by 90 degrees counterclockwise.
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    // Builds a grid of given dimensions where each cell is copied from this
+    // grid at coordinates returned by `source`.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Renders the grid as text, converting each cell into a character using
+    /// the `cell` function. Each row is terminated by a newline.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            text.extend(row.iter().map(&mut cell));
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("coordinates out of bounds")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("coordinates out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Grid<char> {
+        Grid::parse(input, Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_render_work() {
+        let grid = parse("abc\ndef\n");
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get(3, 1), None);
+        assert_eq!(grid.get(2, 2), None);
+        assert_eq!(grid.render(|&ch| ch), "abc\ndef\n");
+
+        let error = Grid::parse("ab\nc\n", Some).unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 1));
+    }
+
+    #[test]
+    fn rows_and_columns_work() {
+        let grid = parse("abc\ndef\n");
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn neighbours_work() {
+        let grid = parse("abc\ndef\n");
+
+        assert_eq!(
+            grid.neighbours_4(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours_8(1, 1).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)]
+        );
+        assert_eq!(
+            grid.neighbours_wrapping(0, 0, &OFFSETS_4)
+                .collect::<Vec<_>>(),
+            vec![(0, 1), (2, 0), (1, 0), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn transpose_and_rotate_work() {
+        let grid = parse("abc\ndef\n");
+
+        assert_eq!(grid.transpose(), parse("ad\nbe\ncf\n"));
+        assert_eq!(grid.rotate_right(), parse("da\neb\nfc\n"));
+        assert_eq!(grid.rotate_left(), parse("cf\nbe\nad\n"));
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+}
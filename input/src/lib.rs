@@ -0,0 +1,300 @@
+// Helpers for parsing puzzle inputs. They cover the input formats that are used
+// repeatedly: grids of characters, comma-separated tuples of numbers, sections
+// separated by blank lines, and labeled lists (`label: a b c`).
+//
+// All errors point to the place in the input where they occurred (line and
+// column) and render a snippet of the input with the place marked by carets.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParseError {
+    message: String,
+    line: usize,
+    column: usize,
+    len: usize,
+    text: String,
+}
+
+impl ParseError {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the line number (starting from 1).
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column number (starting from 1).
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+
+        writeln!(
+            f,
+            "{} (line {}, column {})",
+            self.message, self.line, self.column
+        )?;
+        writeln!(f, "{pad} |")?;
+        writeln!(f, "{number} | {}", self.text)?;
+        write!(
+            f,
+            "{pad} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.len)
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Line of input together with its number (starting from 1).
+#[derive(Copy, Clone, Debug)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Creates an error pointing to the whole line.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        self.error_at(self.text, message)
+    }
+
+    /// Creates an error pointing to a part of the line. The part must be
+    /// a subslice of the line text (e.g. a result of splitting it).
+    pub fn error_at(&self, part: &str, message: impl Into<String>) -> ParseError {
+        let offset = (part.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        assert!(offset <= self.text.len() && offset + part.len() <= self.text.len());
+
+        ParseError {
+            message: message.into(),
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            len: part.chars().count().max(1),
+            text: self.text.to_owned(),
+        }
+    }
+
+    /// Parses a part of the line (see `error_at`), using `what` to describe
+    /// the value in the error message.
+    pub fn parse_part<T: FromStr>(&self, part: &str, what: &str) -> Result<T, ParseError> {
+        part.parse()
+            .map_err(|_| self.error_at(part, format!("invalid {what}: {part:?}")))
+    }
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![];
+    let mut section = vec![];
+
+    for line in lines(input) {
+        if line.text.is_empty() {
+            if !section.is_empty() {
+                sections.push(section);
+                section = vec![];
+            }
+        } else {
+            section.push(line);
+        }
+    }
+
+    if !section.is_empty() {
+        sections.push(section);
+    }
+
+    sections
+}
+
+/// Parses a line containing `N` comma-separated values.
+pub fn tuple<T: FromStr, const N: usize>(line: &Line) -> Result<[T; N], ParseError> {
+    let parts = line.text.split(',').collect::<Vec<_>>();
+
+    if parts.len() != N {
+        return Err(line.error(format!("expected {N} comma-separated values")));
+    }
+
+    let values = parts
+        .into_iter()
+        .map(|part| line.parse_part(part, "value"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(values.try_into().unwrap_or_else(|_| unreachable!()))
+}
+
+/// Parses all lines of the input, each containing `N` comma-separated values.
+pub fn tuples<T: FromStr, const N: usize>(input: &str) -> Result<Vec<[T; N]>, ParseError> {
+    lines(input).map(|line| tuple(&line)).collect()
+}
+
+/// Parses a line in the `label: a b c` format, where the values are separated
+/// by whitespace. The list of values may be empty.
+pub fn labeled_list<'a, T: FromStr>(line: &Line<'a>) -> Result<(&'a str, Vec<T>), ParseError> {
+    let Some((label, values)) = line.text.split_once(':') else {
+        return Err(line.error("missing label"));
+    };
+
+    if label.is_empty() || label.contains(char::is_whitespace) {
+        return Err(line.error_at(label, format!("invalid label: {label:?}")));
+    }
+
+    let values = values
+        .split_whitespace()
+        .map(|value| line.parse_part(value, "value"))
+        .collect::<Result<_, _>>()?;
+
+    Ok((label, values))
+}
+
+/// Parses a non-empty rectangular grid of characters, converting each
+/// character into a cell using the `cell` function (which returns `None` for
+/// invalid characters). Returns the grid as a vector of rows.
+pub fn grid<T>(
+    lines: &[Line],
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let Some(first) = lines.first() else {
+        return Err(ParseError {
+            message: "grid has no rows".to_owned(),
+            line: 1,
+            column: 1,
+            len: 1,
+            text: String::new(),
+        });
+    };
+
+    let width = first.text.chars().count();
+    if width == 0 {
+        return Err(first.error("grid row has no columns"));
+    }
+
+    lines
+        .iter()
+        .map(|line| {
+            if line.text.chars().count() != width {
+                return Err(line.error(format!("grid row doesn't have {width} columns")));
+            }
+
+            line.text
+                .char_indices()
+                .map(|(i, ch)| {
+                    cell(ch).ok_or_else(|| {
+                        let part = &line.text[i..i + ch.len_utf8()];
+
+                        line.error_at(part, format!("invalid grid cell: {ch:?}"))
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_display_works() {
+        let line = Line {
+            number: 12,
+            text: "1,abc,3",
+        };
+        let error = line.error_at(&line.text[2..5], "invalid value");
+
+        assert_eq!(error.line(), 12);
+        assert_eq!(error.column(), 3);
+        assert_eq!(
+            error.to_string(),
+            "invalid value (line 12, column 3)\n   |\n12 | 1,abc,3\n   |   ^^^"
+        );
+    }
+
+    #[test]
+    fn sections_works() {
+        let sections = sections("a\nb\n\n\nc\n\nd\n");
+        let numbers = sections
+            .iter()
+            .map(|section| section.iter().map(|line| line.number).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(numbers, vec![vec![1, 2], vec![5], vec![7]]);
+    }
+
+    #[test]
+    fn tuples_works() {
+        assert_eq!(
+            tuples::<u64, 3>("1,2,3\n4,5,6\n"),
+            Ok(vec![[1, 2, 3], [4, 5, 6]])
+        );
+
+        let error = tuples::<u64, 3>("1,2,3\n4,x,6\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert_eq!(error.message(), "invalid value: \"x\"");
+
+        let error = tuples::<u64, 3>("1,2\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 1));
+        assert_eq!(error.message(), "expected 3 comma-separated values");
+    }
+
+    #[test]
+    fn labeled_list_works() {
+        let line = Line {
+            number: 1,
+            text: "aaa: bbb  ccc",
+        };
+        assert_eq!(
+            labeled_list::<String>(&line),
+            Ok(("aaa", vec!["bbb".to_owned(), "ccc".to_owned()]))
+        );
+
+        let line = Line {
+            number: 1,
+            text: "4x4: 1 a",
+        };
+        let error = labeled_list::<u32>(&line).unwrap_err();
+        assert_eq!(error.column(), 8);
+
+        let line = Line {
+            number: 1,
+            text: "aaa bbb",
+        };
+        assert_eq!(
+            labeled_list::<String>(&line).unwrap_err().message(),
+            "missing label"
+        );
+    }
+
+    #[test]
+    fn grid_works() {
+        let rows = lines(".#\n#.\n").collect::<Vec<_>>();
+        assert_eq!(
+            grid(&rows, |ch| (ch == '#' || ch == '.').then_some(ch == '#')),
+            Ok(vec![vec![false, true], vec![true, false]])
+        );
+
+        let rows = lines(".#\n#x\n").collect::<Vec<_>>();
+        let error = grid(&rows, |ch| (ch == '#' || ch == '.').then_some(ch)).unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 2));
+
+        let rows = lines(".#\n#..\n").collect::<Vec<_>>();
+        let error = grid(&rows, Some).unwrap_err();
+        assert_eq!(error.message(), "grid row doesn't have 2 columns");
+    }
+}
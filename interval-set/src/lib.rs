@@ -0,0 +1,446 @@
+// Set of values stored as sorted ranges, which was originally written for the
+// fresh ingredient IDs of day 5. It works with any type whose values can be
+// stepped through one by one (see `Step`), which is needed to merge adjacent
+// ranges and to compute gaps between them.
+
+use std::ops::RangeInclusive;
+use std::slice;
+
+/// Type whose values can be stepped through one by one. It's a stable
+/// replacement for the unstable `std::iter::Step`.
+pub trait Step: Copy + Ord {
+    /// Returns the next value, or `None` if there isn't any.
+    fn forward(self) -> Option<Self>;
+
+    /// Returns the previous value, or `None` if there isn't any.
+    fn backward(self) -> Option<Self>;
+
+    /// Returns the number of steps from `start` to `end`, which must not be
+    /// less than `start`.
+    fn distance(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_step {
+    ($($t:ty),*) => {
+        $(
+            impl Step for $t {
+                fn forward(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn backward(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn distance(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_step!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct IntervalSet<T> {
+    // We maintain these invariants:
+    //
+    //   1. The ranges are non-empty.
+    //   2. The ranges are non-overlapping and non-adjacent (there is at least
+    //      one value between any two ranges).
+    //   3. The ranges are sorted by their start/end (the first two invariants
+    //      imply that sorting by either leads to the same ordering).
+    //
+    // We rely on these invariants heavily in the implementation. Thanks to
+    // them, two sets are equal if and only if their ranges are.
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+}
+
+impl<T: Step> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet::default()
+    }
+
+    pub fn add_range(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        assert!(start <= end);
+
+        // Index of the first range the newly added range might overlap with or
+        // be adjacent to, or |self.ranges.len()| if there isn't any.
+        let first = self
+            .ranges
+            .partition_point(|range| range.end().forward().is_some_and(|next| next < start));
+
+        // Index of the range *after* the last range the newly added range might
+        // overlap with or be adjacent to, or |0| if there isn't any.
+        let last = self
+            .ranges
+            .partition_point(|range| end.forward().is_none_or(|next| *range.start() <= next));
+
+        debug_assert!(first <= last);
+
+        // If there is no overlapping, insert the newly added range. Otherwise,
+        // build a new range from the newly added range and the ranges it
+        // overlaps with, and replace them with it.
+        if first == last {
+            self.ranges.insert(first, start..=end);
+        } else {
+            let start = start.min(*self.ranges[first].start());
+            let end = end.max(*self.ranges[last - 1].end());
+
+            self.ranges.splice(first..last, [start..=end]);
+        }
+    }
+
+    pub fn remove_range(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        assert!(start <= end);
+
+        // Index of the first range the removed range might overlap with, or
+        // |self.ranges.len()| if there isn't any.
+        let first = self.ranges.partition_point(|range| *range.end() < start);
+
+        // Index of the range *after* the last range the removed range might
+        // overlap with, or |0| if there isn't any.
+        let last = self.ranges.partition_point(|range| *range.start() <= end);
+
+        if first >= last {
+            return;
+        }
+
+        // Only the parts of the first and the last overlapping ranges that are
+        // outside of the removed range are kept.
+        let mut kept = vec![];
+
+        if *self.ranges[first].start() < start {
+            kept.push(*self.ranges[first].start()..=start.backward().unwrap());
+        }
+
+        if *self.ranges[last - 1].end() > end {
+            kept.push(end.forward().unwrap()..=*self.ranges[last - 1].end());
+        }
+
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // Index of the only range the value might fall into.
+        let index = self.ranges.partition_point(|range| *range.end() < value);
+
+        self.ranges
+            .get(index)
+            .is_some_and(|range| *range.start() <= value)
+    }
+
+    /// Returns the ranges of the set. They are sorted, and there is at least
+    /// one value between any two of them.
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, RangeInclusive<T>> {
+        self.ranges.iter()
+    }
+
+    /// Returns the ranges of values between the ranges of the set, in order.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.windows(2).map(|ranges| {
+            ranges[0].end().forward().unwrap()..=ranges[1].start().backward().unwrap()
+        })
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|range| T::distance(*range.start(), *range.end()) + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = self.ranges.iter().chain(&other.ranges).collect::<Vec<_>>();
+        ranges.sort_by_key(|range| range.start());
+
+        // As the ranges are sorted by their start, each range either can be
+        // merged with the last one, or it's after it.
+        let mut union = IntervalSet::<T>::new();
+
+        for range in ranges {
+            match union.ranges.last_mut() {
+                Some(last)
+                    if last
+                        .end()
+                        .forward()
+                        .is_none_or(|next| next >= *range.start()) =>
+                {
+                    *last = *last.start()..=*last.end().max(range.end());
+                }
+                _ => union.ranges.push(range.clone()),
+            }
+        }
+
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intersection = IntervalSet::new();
+        let (mut i, mut j) = (0, 0);
+
+        // Both sets are walked through at once, always moving past the range
+        // that ends first, as it can't overlap with any of the remaining ones.
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+
+            if start <= end {
+                intersection.ranges.push(start..=end);
+            }
+
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        intersection
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        match (self.ranges.first(), self.ranges.last()) {
+            (Some(first), Some(last)) => {
+                self.intersection(&other.complement(*first.start()..=*last.end()))
+            }
+            _ => IntervalSet::new(),
+        }
+    }
+
+    /// Returns the values within the bounds that aren't in the set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> IntervalSet<T> {
+        let (start, end) = bounds.into_inner();
+        assert!(start <= end);
+
+        let mut complement = IntervalSet::new();
+
+        // Start of the next range of the complement, or `None` if the set
+        // contains the maximum value.
+        let mut next = Some(start);
+
+        for range in &self.ranges {
+            let Some(start) = next else {
+                break;
+            };
+
+            if *range.start() > end {
+                break;
+            }
+
+            if *range.start() > start {
+                complement
+                    .ranges
+                    .push(start..=range.start().backward().unwrap());
+            }
+
+            if *range.end() >= start {
+                next = range.end().forward();
+            }
+        }
+
+        if let Some(start) = next
+            && start <= end
+        {
+            complement.ranges.push(start..=end);
+        }
+
+        complement
+    }
+}
+
+impl<T: Step> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+
+        for range in ranges {
+            set.add_range(range);
+        }
+
+        set
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a RangeInclusive<T>;
+    type IntoIter = slice::Iter<'a, RangeInclusive<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn add_range_works() {
+        let mut set = IntervalSet::new();
+        set.add_range(3..=5);
+        set.add_range(10..=14);
+        set.add_range(16..=20);
+        set.add_range(12..=18);
+
+        assert_eq!(set.ranges(), [3..=5, 10..=20]);
+
+        set.add_range(6..=6);
+        set.add_range(8..=9);
+
+        assert_eq!(set.ranges(), [3..=6, 8..=20]);
+        assert!(!set.contains(1));
+        assert!(set.contains(5));
+        assert!(!set.contains(7));
+        assert!(set.contains(20));
+        assert!(!set.contains(32));
+        assert_eq!(set.len(), 17);
+
+        let mut set = IntervalSet::new();
+        set.add_range(u64::MAX..=u64::MAX);
+        set.add_range(0..=u64::MAX - 1);
+
+        assert_eq!(set.ranges(), [0..=u64::MAX]);
+        assert_eq!(set.len(), 1 << 64);
+    }
+
+    #[test]
+    fn remove_range_works() {
+        let mut set = [3..=6, 8..=20].into_iter().collect::<IntervalSet<i32>>();
+
+        set.remove_range(10..=12);
+        assert_eq!(set.ranges(), [3..=6, 8..=9, 13..=20]);
+
+        set.remove_range(5..=8);
+        assert_eq!(set.ranges(), [3..=4, 9..=9, 13..=20]);
+
+        set.remove_range(-5..=9);
+        assert_eq!(set.ranges(), [13..=20]);
+
+        set.remove_range(21..=30);
+        assert_eq!(set.ranges(), [13..=20]);
+    }
+
+    #[test]
+    fn set_operations_work() {
+        let a = [0..=4, 10..=14].into_iter().collect::<IntervalSet<u8>>();
+        let b = [3..=11, 14..=20].into_iter().collect::<IntervalSet<u8>>();
+
+        assert_eq!(a.union(&b).ranges(), [0..=20]);
+        assert_eq!(a.intersection(&b).ranges(), [3..=4, 10..=11, 14..=14]);
+        assert_eq!(a.difference(&b).ranges(), [0..=2, 12..=13]);
+        assert_eq!(b.difference(&a).ranges(), [5..=9, 15..=20]);
+        assert_eq!(a.complement(2..=255).ranges(), [5..=9, 15..=255]);
+        assert_eq!(a.gaps().collect::<Vec<_>>(), [5..=9]);
+    }
+
+    // Creates a set of the given ranges, some of which may be empty.
+    fn set_and_model(ranges: &[(u8, u8)]) -> (IntervalSet<u8>, BTreeSet<u8>) {
+        let ranges = ranges
+            .iter()
+            .filter(|(start, end)| start <= end)
+            .map(|&(start, end)| start..=end);
+
+        (ranges.clone().collect(), ranges.flatten().collect())
+    }
+
+    fn values(set: &IntervalSet<u8>) -> BTreeSet<u8> {
+        set.iter().cloned().flatten().collect()
+    }
+
+    // Checks that the invariants of the set hold.
+    fn is_valid(set: &IntervalSet<u8>) -> bool {
+        set.iter().all(|range| range.start() <= range.end())
+            && set
+                .ranges()
+                .windows(2)
+                .all(|ranges| *ranges[0].end() as u16 + 1 < *ranges[1].start() as u16)
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<(u8, u8)>> {
+        vec((any::<u8>(), 0..20u8), 0..10).prop_map(|ranges| {
+            ranges
+                .into_iter()
+                .map(|(start, len)| (start, start.saturating_add(len)))
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn set_matches_model(ranges in ranges(), removed in ranges()) {
+            let (mut set, mut model) = set_and_model(&ranges);
+
+            prop_assert!(is_valid(&set));
+            prop_assert_eq!(values(&set), model.clone());
+            prop_assert_eq!(set.len(), model.len() as u128);
+
+            for value in 0..=u8::MAX {
+                prop_assert_eq!(set.contains(value), model.contains(&value));
+            }
+
+            for (start, end) in removed {
+                set.remove_range(start..=end);
+                model.retain(|value| !(start..=end).contains(value));
+            }
+
+            prop_assert!(is_valid(&set));
+            prop_assert_eq!(values(&set), model);
+        }
+
+        #[test]
+        fn set_operations_match_model(a in ranges(), b in ranges(), start: u8, end: u8) {
+            let (a, a_model) = set_and_model(&a);
+            let (b, b_model) = set_and_model(&b);
+
+            for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                prop_assert!(is_valid(&set));
+            }
+
+            prop_assert_eq!(values(&a.union(&b)), &a_model | &b_model);
+            prop_assert_eq!(values(&a.intersection(&b)), &a_model & &b_model);
+            prop_assert_eq!(values(&a.difference(&b)), &a_model - &b_model);
+
+            let (start, end) = (start.min(end), start.max(end));
+            let complement = a.complement(start..=end);
+
+            prop_assert!(is_valid(&complement));
+            prop_assert_eq!(
+                values(&complement),
+                (start..=end).filter(|value| !a_model.contains(value)).collect()
+            );
+
+            let gaps = a.gaps().flatten().collect::<BTreeSet<_>>();
+
+            match (a_model.first(), a_model.last()) {
+                (Some(&first), Some(&last)) => prop_assert_eq!(
+                    gaps,
+                    (first..=last).filter(|value| !a_model.contains(value)).collect()
+                ),
+                _ => prop_assert!(gaps.is_empty()),
+            }
+        }
+    }
+}
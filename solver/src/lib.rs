@@ -0,0 +1,184 @@
+use std::{env, fmt, io, process};
+
+use anyhow::{Result, anyhow, bail};
+use report::Report;
+
+pub mod report;
+
+/// Answer to one part of a puzzle.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Answer {
+    Number(u128),
+    /// The puzzle doesn't have the part (this is the case of day 12 part 2).
+    None,
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Self {
+        Answer::None
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(n as u128)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Number(n as u128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as u128)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::None => Ok(()),
+        }
+    }
+}
+
+/// Day-specific options, which can be given as command line arguments of the
+/// day binary.
+pub trait Options: Default {
+    /// Usage of the options, e.g. `[--threshold <N>]`.
+    const USAGE: &'static str;
+
+    /// Parses the given option, taking its value from `args` if it has one.
+    /// Returns `false` if the option is unknown.
+    fn parse_option(&mut self, option: &str, args: &mut dyn Iterator<Item = &str>) -> Result<bool>;
+
+    /// Checks that the options are consistent with each other. Called after all
+    /// options are parsed.
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Options for () {
+    const USAGE: &'static str = "";
+
+    fn parse_option(&mut self, _: &str, _: &mut dyn Iterator<Item = &str>) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Returns the value of the given option, which is the next argument.
+pub fn option_value<'a>(args: &mut dyn Iterator<Item = &'a str>, option: &str) -> Result<&'a str> {
+    args.next()
+        .ok_or_else(|| anyhow!("missing value of option: {option:?}"))
+}
+
+/// Solution to a day's puzzle.
+///
+/// The input is parsed only once and then passed to the solutions of both parts
+/// of the puzzle, which can therefore be run (and measured) separately. Options
+/// are passed only to `parse`, so if the parts depend on them, they need to be
+/// made part of the input.
+pub trait Solver {
+    type Input;
+    type Options: Options;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+    type Error: Into<anyhow::Error>;
+
+    fn parse(input: &str, options: &Self::Options) -> Result<Self::Input, Self::Error>;
+
+    fn part_1(input: &Self::Input) -> Result<Self::Answer1, Self::Error>;
+
+    fn part_2(input: &Self::Input) -> Result<Self::Answer2, Self::Error>;
+
+    /// Returns warnings about the parsed input, e.g. about lines that were
+    /// skipped in some part.
+    fn warnings(_input: &Self::Input) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// Parses arguments of a day binary and returns whether the output should be
+/// JSON and the day-specific options.
+pub fn parse_args<S: Solver>(args: &[String]) -> Result<(bool, S::Options)> {
+    let mut args = args.iter().map(String::as_str);
+
+    let mut json = false;
+    let mut options = S::Options::default();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--format" => match option_value(&mut args, arg)? {
+                "text" => json = false,
+                "json" => json = true,
+                format => bail!("invalid format: {format:?}"),
+            },
+            _ => {
+                if !options.parse_option(arg, &mut args)? {
+                    bail!("unknown option: {arg:?}");
+                }
+            }
+        }
+    }
+
+    options.validate()?;
+
+    Ok((json, options))
+}
+
+/// Implements the binary of the given day. It reads the input from the standard
+/// input and writes the answers to the standard output, either as plain text
+/// (one per line) or as JSON (with `--format json`, see `report`).
+pub fn main<S: Solver>(day: usize) {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let (json, options) = match parse_args::<S>(&args) {
+        Ok(args) => args,
+        Err(e) => {
+            let usage = format!(
+                "usage: day-{day} [--format <text|json>] {}",
+                S::Options::USAGE
+            );
+
+            eprintln!("{e}\n\n{}", usage.trim_end());
+            process::exit(2);
+        }
+    };
+
+    let report = match io::read_to_string(io::stdin()) {
+        Ok(input) => report::run_with::<S>(day, &input, None, &options),
+        Err(e) => Report::io_error(day, e),
+    };
+
+    // In JSON, warnings are part of the report.
+    if !json {
+        for warning in &report.warnings {
+            eprintln!("warning: {warning}");
+        }
+    }
+
+    if json {
+        println!("{}", report.to_json());
+    } else if let Some(error) = &report.error {
+        eprintln!("{}", error.message);
+    } else {
+        for (_, answer) in report.answers() {
+            println!("{answer}");
+        }
+    }
+
+    if report.error.is_some() {
+        process::exit(1);
+    }
+}
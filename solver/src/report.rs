@@ -0,0 +1,274 @@
+// Reports of running a solution, which can be output either as plain text (the
+// answers, one per line) or as JSON for consumption by other tools.
+//
+// Errors are reported with a stable kind, which depends only on the step in
+// which they occurred (reading, parsing, or solving a part), and with a
+// free-form message, which may change at any time.
+
+use std::fmt::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::{Answer, Solver};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ErrorKind {
+    /// The input can't be read.
+    Io,
+    /// The input can't be parsed.
+    InvalidInput,
+    /// The input was parsed, but a part of the puzzle can't be solved for it.
+    Unsolvable,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Io => "io",
+            ErrorKind::InvalidInput => "invalid_input",
+            ErrorKind::Unsolvable => "unsolvable",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl Error {
+    fn new(kind: ErrorKind, error: impl Into<anyhow::Error>) -> Error {
+        Error {
+            kind,
+            message: format!("{:#}", error.into()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Timings {
+    pub parse: Option<Duration>,
+    pub part_1: Option<Duration>,
+    pub part_2: Option<Duration>,
+}
+
+/// Report of running a solution. Parts that weren't run (because they weren't
+/// requested or because an error occurred) don't have an answer or a timing.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub day: usize,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+    pub timings: Timings,
+    /// Warnings about the input (see `Solver::warnings`).
+    pub warnings: Vec<String>,
+    pub error: Option<Error>,
+}
+
+impl Report {
+    fn new(day: usize) -> Report {
+        Report {
+            day,
+            part_1: None,
+            part_2: None,
+            timings: Timings::default(),
+            warnings: vec![],
+            error: None,
+        }
+    }
+
+    /// Creates a report of a failure to read the input.
+    pub fn io_error(day: usize, error: impl Into<anyhow::Error>) -> Report {
+        Report {
+            error: Some(Error::new(ErrorKind::Io, error)),
+            ..Report::new(day)
+        }
+    }
+
+    /// Returns answers to the parts that were run, except for nonexistent ones
+    /// (day 12 part 2).
+    pub fn answers(&self) -> Vec<(u32, Answer)> {
+        [(1, self.part_1), (2, self.part_2)]
+            .into_iter()
+            .filter_map(|(part, answer)| match answer {
+                Some(Answer::None) | None => None,
+                Some(answer) => Some((part, answer)),
+            })
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+
+        write!(
+            json,
+            r#"{{"day":{},"part1":{},"part2":{},"timings":{{"parse_ns":{},"part1_ns":{},"part2_ns":{}}},"warnings":[{}],"error":"#,
+            self.day,
+            answer_json(self.part_1),
+            answer_json(self.part_2),
+            duration_json(self.timings.parse),
+            duration_json(self.timings.part_1),
+            duration_json(self.timings.part_2),
+            self.warnings
+                .iter()
+                .map(|warning| string_json(warning))
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .unwrap();
+
+        match &self.error {
+            Some(error) => write!(
+                json,
+                r#"{{"kind":"{}","message":{}}}}}"#,
+                error.kind,
+                string_json(&error.message)
+            )
+            .unwrap(),
+            None => json.push_str("null}"),
+        }
+
+        json
+    }
+}
+
+fn answer_json(answer: Option<Answer>) -> String {
+    match answer {
+        Some(Answer::Number(n)) => n.to_string(),
+        Some(Answer::None) | None => "null".to_owned(),
+    }
+}
+
+fn duration_json(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => duration.as_nanos().to_string(),
+        None => "null".to_owned(),
+    }
+}
+
+fn string_json(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+
+    json.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            ch if ch.is_control() => write!(json, "\\u{:04x}", ch as u32).unwrap(),
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+
+    json
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
+
+/// Parses the input and solves the given part of the puzzle (or both parts if
+/// no part is given), measuring how long each step takes. Default options are
+/// used.
+pub fn run<S: Solver>(day: usize, input: &str, part: Option<u32>) -> Report {
+    run_with::<S>(day, input, part, &S::Options::default())
+}
+
+/// Like `run`, but with the given options.
+pub fn run_with<S: Solver>(
+    day: usize,
+    input: &str,
+    part: Option<u32>,
+    options: &S::Options,
+) -> Report {
+    let mut report = Report::new(day);
+
+    let (input, duration) = timed(|| S::parse(input, options));
+    report.timings.parse = Some(duration);
+
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            report.error = Some(Error::new(ErrorKind::InvalidInput, e));
+            return report;
+        }
+    };
+
+    report.warnings = S::warnings(&input);
+
+    if part.is_none_or(|part| part == 1) {
+        let (answer, duration) = timed(|| S::part_1(&input));
+        report.timings.part_1 = Some(duration);
+
+        match answer {
+            Ok(answer) => report.part_1 = Some(answer.into()),
+            Err(e) => {
+                report.error = Some(Error::new(ErrorKind::Unsolvable, e));
+                return report;
+            }
+        }
+    }
+
+    if part.is_none_or(|part| part == 2) {
+        let (answer, duration) = timed(|| S::part_2(&input));
+        report.timings.part_2 = Some(duration);
+
+        match answer {
+            Ok(answer) => report.part_2 = Some(answer.into()),
+            Err(e) => {
+                report.error = Some(Error::new(ErrorKind::Unsolvable, e));
+                return report;
+            }
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_json_works() {
+        let report = Report {
+            day: 7,
+            part_1: Some(Answer::Number(21)),
+            part_2: None,
+            timings: Timings {
+                parse: Some(Duration::from_nanos(1500)),
+                part_1: Some(Duration::from_nanos(300)),
+                part_2: None,
+            },
+            warnings: vec!["skipped \"x\"".to_owned(), "y".to_owned()],
+            error: Some(Error {
+                kind: ErrorKind::Unsolvable,
+                message: "invalid \"x\"\n".to_owned(),
+            }),
+        };
+
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":7,"part1":21,"part2":null,"timings":{"parse_ns":1500,"part1_ns":300,"part2_ns":null},"warnings":["skipped \"x\"","y"],"error":{"kind":"unsolvable","message":"invalid \"x\"\n"}}"#
+        );
+
+        let report = Report::new(12);
+
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":12,"part1":null,"part2":null,"timings":{"parse_ns":null,"part1_ns":null,"part2_ns":null},"warnings":[],"error":null}"#
+        );
+    }
+}